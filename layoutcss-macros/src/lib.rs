@@ -0,0 +1,62 @@
+use layoutcss_parser::macro_usage::{crate_config_path, validate_invocation};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, LitStr, Token};
+
+/// The arguments of `layout!`: the tag of the element and its layout attribute.
+struct Invocation {
+    tag: LitStr,
+    classes: LitStr,
+}
+
+impl Parse for Invocation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let tag = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let classes = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Invocation { tag, classes })
+    }
+}
+
+/// Check the layout attribute of an element at compile time and expand to it.
+///
+/// Every class must exist and have an effect on the tag, so typos don't compile.
+/// They are checked with the closest `layoutcss.toml` of the crate, so the tag
+/// is written with its element naming, like `layout!("lc-row", "gap:2")`,
+/// and the crate is checked again when the file changes:
+///
+/// ```
+/// use layoutcss_macros::layout;
+///
+/// let attribute: &str = layout!("row-l", "gap:2 justify:center");
+/// assert_eq!(attribute, "gap:2 justify:center");
+/// ```
+///
+/// ```compile_fail
+/// use layoutcss_macros::layout;
+///
+/// let attribute = layout!("row-l", "gap:2 justfy:center");
+/// ```
+///
+/// The css of the invocations is generated by reading the rust sources,
+/// with `layoutcss_parser::macro_usage::invocations_markup`, from a build script.
+#[proc_macro]
+pub fn layout(input: TokenStream) -> TokenStream {
+    let Invocation { tag, classes } = parse_macro_input!(input as Invocation);
+    // including the config makes cargo check the invocations again when it changes
+    let tracked_config = crate_config_path().map(|config_path| {
+        let config_path = config_path.to_string_lossy().into_owned();
+        quote!(const _: &[u8] = include_bytes!(#config_path);)
+    });
+    match validate_invocation(&tag.value(), &classes.value()) {
+        Ok(()) => quote!({ #tracked_config #classes }).into(),
+        Err(problems) => {
+            let errors = problems
+                .iter()
+                .map(|problem| syn::Error::new(classes.span(), problem).to_compile_error());
+            quote!({ #tracked_config #(#errors)* }).into()
+        }
+    }
+}
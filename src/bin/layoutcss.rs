@@ -0,0 +1,373 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use layoutcss_parser::compiler::LayoutCompiler;
+use layoutcss_parser::config::{find_config_file, LayoutStyleConfig};
+use layoutcss_parser::diagnostic::Diagnostic;
+use layoutcss_parser::files::collect_files;
+use layoutcss_parser::manifest::{DocumentManifest, Manifest};
+use layoutcss_parser::media_query::MediaQuery;
+use layoutcss_parser::source_map::RuleOrigins;
+use layoutcss_parser::{
+    generate_final_css_with_config, generate_final_css_with_source_map, insert_css_rules,
+    insert_css_rules_with_origins,
+};
+
+const USAGE: &str = "\
+Generate the css of the layoutcss components and utilities used in your templates.
+
+Usage: layoutcss [OPTIONS] <INPUT>...
+       layoutcss watch [OPTIONS] --output <FILE> <INPUT>...
+
+Inputs can be files, directories (searched recursively for templates) or globs like 'templates/**/*.html'.
+With `watch`, the inputs are checked for changes and the stylesheet is regenerated each time
+a file is modified, added or removed.
+
+Options:
+  -o, --output <FILE>    write the stylesheet into FILE instead of stdout
+  -c, --config <FILE>    configuration file to use, by default layoutcss.toml is searched
+                         in the current directory and its parents
+      --strict           exit with an error if a problem is found in the markup
+      --minify           write a minified stylesheet, whatever the configuration says
+      --source-map       write the source map of the stylesheet next to the output file,
+                         linking each rule to the template producing it (not with `watch`)
+      --manifest <FILE>  write into FILE the json report of the components, classes
+                         and breakpoints found in each template (not with `watch`)
+      --interval <MS>    with `watch`, time between two checks of the files (default 300)
+  -h, --help             print this help
+";
+
+#[derive(Debug)]
+struct Options {
+    watch: bool,
+    inputs: Vec<String>,
+    output: Option<PathBuf>,
+    config: Option<PathBuf>,
+    strict: bool,
+    minify: bool,
+    source_map: bool,
+    manifest: Option<PathBuf>,
+    interval: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            watch: false,
+            inputs: vec![],
+            output: None,
+            config: None,
+            strict: false,
+            minify: false,
+            source_map: false,
+            manifest: None,
+            interval: Duration::from_millis(300),
+        }
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "watch" if !options.watch && options.inputs.is_empty() => options.watch = true,
+            "-o" | "--output" => {
+                let value = args.next().ok_or(format!("{arg} expects a file"))?;
+                options.output = Some(PathBuf::from(value));
+            }
+            "-c" | "--config" => {
+                let value = args.next().ok_or(format!("{arg} expects a file"))?;
+                options.config = Some(PathBuf::from(value));
+            }
+            "--strict" => options.strict = true,
+            "--minify" => options.minify = true,
+            "--source-map" => options.source_map = true,
+            "--manifest" => {
+                let value = args.next().ok_or(format!("{arg} expects a file"))?;
+                options.manifest = Some(PathBuf::from(value));
+            }
+            "--interval" => {
+                let value = args.next().ok_or(format!("{arg} expects a duration in ms"))?;
+                let ms = value
+                    .parse::<u64>()
+                    .map_err(|_| format!("{arg} expects a duration in ms, got {value}"))?;
+                options.interval = Duration::from_millis(ms);
+            }
+            "--" => options.inputs.extend(args.by_ref()),
+            a if a.starts_with('-') && a.len() > 1 => return Err(format!("unknown option {a}")),
+            _ => options.inputs.push(arg),
+        }
+    }
+    if options.inputs.is_empty() {
+        return Err("no input given".to_string());
+    }
+    if options.watch && options.output.is_none() {
+        return Err("watch needs an --output file".to_string());
+    }
+    if options.source_map && options.watch {
+        return Err("--source-map can't be used with watch".to_string());
+    }
+    if options.manifest.is_some() && options.watch {
+        return Err("--manifest can't be used with watch".to_string());
+    }
+    if options.source_map && options.output.is_none() {
+        return Err("--source-map needs an --output file".to_string());
+    }
+    Ok(Some(options))
+}
+
+/// return the name of the file, without its directory
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().to_string())
+}
+
+/// return the path of `file` from the directory of `output`,
+/// or its absolute path if it can't be found
+fn relative_path(file: &Path, output: &Path) -> String {
+    let output_dir = match output.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let (Ok(file), Ok(output_dir)) = (file.canonicalize(), output_dir.canonicalize()) else {
+        return file.display().to_string();
+    };
+    let common = file
+        .components()
+        .zip(output_dir.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in output_dir.components().skip(common) {
+        relative.push("..");
+    }
+    relative.extend(file.components().skip(common));
+    // source maps use urls, so the separator is always `/`
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn load_config(options: &Options) -> Result<LayoutStyleConfig, String> {
+    let mut config = match &options.config {
+        Some(path) => LayoutStyleConfig::from_path(path),
+        None => LayoutStyleConfig::discover("."),
+    }
+    .map_err(|e| e.to_string())?;
+    if options.minify {
+        config.minify = true;
+    }
+    if options.source_map && config.minify {
+        return Err("the source map can't be written for a minified stylesheet".to_string());
+    }
+    Ok(config)
+}
+
+fn print_diagnostics(file: &Path, diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        eprintln!("{}", diagnostic.in_file(file));
+    }
+}
+
+fn write_css(output: &Option<PathBuf>, css: &str) -> Result<(), String> {
+    match output {
+        Some(path) => fs::write(path, css).map_err(|e| format!("{}: {e}", path.display())),
+        None => {
+            print!("{css}");
+            Ok(())
+        }
+    }
+}
+
+fn build(options: &Options) -> Result<ExitCode, String> {
+    let config = load_config(options)?;
+    let files = collect_files(&options.inputs).map_err(|e| e.to_string())?;
+    if files.is_empty() {
+        return Err("no file matches the inputs".to_string());
+    }
+
+    // the rules are accumulated file after file
+    let mut css_rules: HashSet<String> = HashSet::new();
+    let mut css_mq_rules: HashMap<MediaQuery, HashSet<String>> = HashMap::new();
+    let mut origins = RuleOrigins::new();
+    let mut texts = vec![];
+    let mut diagnostics_count = 0;
+    for file in &files {
+        let text = fs::read_to_string(file).map_err(|e| format!("{}: {e}", file.display()))?;
+        let diagnostics = match &options.output {
+            Some(output) if options.source_map => {
+                // the sources of the map are relative to the map
+                let source = origins.add_source(&relative_path(file, output));
+                insert_css_rules_with_origins(
+                    &text,
+                    &mut css_rules,
+                    &mut css_mq_rules,
+                    &config,
+                    &mut origins,
+                    source,
+                )
+            }
+            _ => insert_css_rules(&text, &mut css_rules, &mut css_mq_rules, &config),
+        };
+        print_diagnostics(file, &diagnostics);
+        diagnostics_count += diagnostics.len();
+        if options.manifest.is_some() {
+            texts.push(text);
+        }
+    }
+    if let Some(manifest_path) = &options.manifest {
+        let mut manifest = Manifest::new();
+        for (file, text) in files.iter().zip(&texts) {
+            let path = file.display().to_string();
+            manifest.documents.push(DocumentManifest::parse(&path, text, &config));
+        }
+        fs::write(manifest_path, manifest.to_json())
+            .map_err(|e| format!("{}: {e}", manifest_path.display()))?;
+    }
+    match &options.output {
+        Some(output) if options.source_map => {
+            let css_file = file_name(output);
+            let (css, source_map) = generate_final_css_with_source_map(
+                &css_rules,
+                &css_mq_rules,
+                &config,
+                &origins,
+                &css_file,
+            );
+            let map_path = PathBuf::from(format!("{}.map", output.display()));
+            fs::write(&map_path, source_map).map_err(|e| format!("{}: {e}", map_path.display()))?;
+            let css = format!("{css}\n/*# sourceMappingURL={css_file}.map */\n");
+            write_css(&options.output, &css)?;
+        }
+        _ => {
+            let css = generate_final_css_with_config(&css_rules, &css_mq_rules, &config);
+            write_css(&options.output, &css)?
+        }
+    }
+
+    if options.strict && diagnostics_count > 0 {
+        eprintln!("{diagnostics_count} problem(s) found");
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// modification time and size of a file, to know if it changed since it was parsed
+type Stamp = (Option<SystemTime>, u64);
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
+fn watch(options: &Options) -> Result<ExitCode, String> {
+    let config_path = match &options.config {
+        Some(path) => Some(path.clone()),
+        None => find_config_file("."),
+    };
+    let mut config_stamp = config_path.as_deref().and_then(stamp);
+    let mut compiler = LayoutCompiler::new(load_config(options)?);
+    let mut stamps: HashMap<PathBuf, Stamp> = HashMap::new();
+    let mut first_run = true;
+
+    loop {
+        let mut changed = first_run;
+        first_run = false;
+
+        // when the configuration changes, every file has to be processed again
+        let new_config_stamp = config_path.as_deref().and_then(stamp);
+        if new_config_stamp != config_stamp {
+            config_stamp = new_config_stamp;
+            match load_config(options) {
+                Ok(config) => {
+                    compiler = LayoutCompiler::new(config);
+                    stamps.clear();
+                    changed = true;
+                }
+                Err(error) => eprintln!("error: {error}"),
+            }
+        }
+
+        let files = match collect_files(&options.inputs) {
+            Ok(files) => files,
+            Err(error) => {
+                eprintln!("error: {error}");
+                vec![]
+            }
+        };
+
+        // the rules of the files which disappeared are retracted
+        let removed: Vec<PathBuf> = stamps
+            .keys()
+            .filter(|path| !files.contains(path))
+            .cloned()
+            .collect();
+        for path in removed {
+            stamps.remove(&path);
+            compiler.remove_document(&path.to_string_lossy());
+            changed = true;
+        }
+
+        for file in files {
+            let Some(file_stamp) = stamp(&file) else {
+                continue;
+            };
+            if stamps.get(&file) == Some(&file_stamp) {
+                continue;
+            }
+            let text = match fs::read_to_string(&file) {
+                Ok(text) => text,
+                Err(error) => {
+                    eprintln!("{}: {error}", file.display());
+                    continue;
+                }
+            };
+            let diagnostics = compiler.update_document(&file.to_string_lossy(), &text);
+            print_diagnostics(&file, &diagnostics);
+            stamps.insert(file, file_stamp);
+            changed = true;
+        }
+
+        if changed {
+            match write_css(&options.output, &compiler.css()) {
+                Ok(()) => eprintln!(
+                    "stylesheet generated from {} file(s)",
+                    compiler.documents_count()
+                ),
+                Err(error) => eprintln!("error: {error}"),
+            }
+        }
+        thread::sleep(options.interval);
+    }
+}
+
+fn main() -> ExitCode {
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => {
+            let result = if options.watch {
+                watch(&options)
+            } else {
+                build(&options)
+            };
+            result.unwrap_or_else(|error| {
+                eprintln!("error: {error}");
+                ExitCode::from(2)
+            })
+        }
+        Ok(None) => {
+            print!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
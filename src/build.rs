@@ -0,0 +1,171 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::{find_config_file, ConfigError, LayoutStyleConfig};
+use crate::files::{has_extension, walk_dir, TEMPLATE_EXTENSIONS};
+use crate::macro_usage::{invocations_markup, string_literals};
+use crate::{generate_final_css_with_config, insert_css_rules};
+
+#[derive(Debug)]
+pub enum BuildError {
+    /// A file or a directory couldn't be read or written.
+    Io { path: PathBuf, source: io::Error },
+    /// The `layoutcss.toml` found for the sources is not valid.
+    Config(ConfigError),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::Config(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Config(error) => Some(error),
+        }
+    }
+}
+
+impl From<ConfigError> for BuildError {
+    fn from(error: ConfigError) -> Self {
+        BuildError::Config(error)
+    }
+}
+
+/// Generate the stylesheet of the templates and rust files of `src_dir`, to be called
+/// from a build script, and return the path of the stylesheet written.
+/// A relative `out_file` is written in `OUT_DIR`, so it can be included with
+/// `include_str!(concat!(env!("OUT_DIR"), "/layout.css"))`.
+/// In rust files, the whole text (for the markup of macros like `view!`),
+/// the string literals and the `layout!` invocations are parsed.
+/// The configuration is the closest `layoutcss.toml`, and cargo is asked
+/// to run the build script again when it or one of the scanned files changes.
+pub fn compile_dir(
+    src_dir: impl AsRef<Path>,
+    out_file: impl AsRef<Path>,
+) -> Result<PathBuf, BuildError> {
+    let src_dir = src_dir.as_ref();
+    let config = match find_config_file(src_dir) {
+        Some(config_path) => {
+            println!("cargo:rerun-if-changed={}", config_path.display());
+            LayoutStyleConfig::from_path(config_path)?
+        }
+        None => LayoutStyleConfig::default(),
+    };
+    // a new file in the directory changes its modification time
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| BuildError::Io { path, source }
+    };
+    let extensions: Vec<&str> = TEMPLATE_EXTENSIONS.iter().copied().chain(["rs"]).collect();
+    let mut css_rules = HashSet::new();
+    let mut css_mq_rules = HashMap::new();
+    for file in walk_dir(src_dir, &extensions).map_err(io_error(src_dir))? {
+        println!("cargo:rerun-if-changed={}", file.display());
+        let text = fs::read_to_string(&file).map_err(io_error(&file))?;
+        let diagnostics = insert_css_rules(&text, &mut css_rules, &mut css_mq_rules, &config);
+        if has_extension(&file, &["rs"]) {
+            // the problems of the rust files are left to the layout! macro,
+            // the markup escaped in their strings would only give false ones
+            let markup = string_literals(&text).join("\n") + "\n" + &invocations_markup(&text, &config);
+            insert_css_rules(&markup, &mut css_rules, &mut css_mq_rules, &config);
+        } else {
+            for diagnostic in diagnostics {
+                println!("cargo:warning={}", diagnostic.in_file(&file));
+            }
+        }
+    }
+
+    let out_file = out_file.as_ref();
+    let out_path = match std::env::var_os("OUT_DIR") {
+        Some(out_dir) if out_file.is_relative() => Path::new(&out_dir).join(out_file),
+        _ => out_file.to_path_buf(),
+    };
+    if let Some(parent) = out_path.parent() {
+        fs::create_dir_all(parent).map_err(io_error(parent))?;
+    }
+    let css = generate_final_css_with_config(&css_rules, &css_mq_rules, &config);
+    fs::write(&out_path, css).map_err(io_error(&out_path))?;
+    Ok(out_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_files_and_templates_are_compiled() {
+        let root = std::env::temp_dir().join(format!("layoutcss-build-{}", std::process::id()));
+        let src = root.join("src");
+        fs::create_dir_all(src.join("templates")).unwrap();
+        fs::write(
+            src.join("app.rs"),
+            r#"
+            fn view() -> String { "<row-l layout=\"gap:3\">".to_string() }
+            const STACK: &str = layout!("stack-l", "gap:4");
+            "#,
+        )
+        .unwrap();
+        fs::write(
+            src.join("templates").join("a.html"),
+            "<box-l layout=\"p:5\">",
+        )
+        .unwrap();
+        fs::write(src.join("notes.txt"), "<box-l layout=\"p:6\">").unwrap();
+
+        let out_path = compile_dir(&src, root.join("out").join("layout.css")).unwrap();
+        let css = fs::read_to_string(&out_path).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(out_path, root.join("out").join("layout.css"));
+        for selector in [
+            "row-l[layout~=\"gap:3\"]",
+            "stack-l[layout~=\"gap:4\"]",
+            "[layout~=\"p:5\"]",
+        ] {
+            assert!(css.contains(selector), "{selector} missing");
+        }
+        assert!(!css.contains("p:6"));
+    }
+
+    #[test]
+    fn rust_files_are_compiled_with_the_config() {
+        let root = std::env::temp_dir().join(format!("layoutcss-build-config-{}", std::process::id()));
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            root.join("layoutcss.toml"),
+            "attribute_name = \"data-layout\"\nelement_name = \"lc-{name}\"",
+        )
+        .unwrap();
+        fs::write(
+            src.join("app.rs"),
+            r#"
+            // a 5" screen
+            fn view() -> String { "<lc-row data-layout=\"gap:3\">".to_string() }
+            const STACK: &str = layout!("lc-stack", "gap:4");
+            "#,
+        )
+        .unwrap();
+
+        let out_path = compile_dir(&src, root.join("layout.css")).unwrap();
+        let css = fs::read_to_string(&out_path).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        for selector in [
+            "lc-row[data-layout~=\"gap:3\"]",
+            "lc-stack[data-layout~=\"gap:4\"]",
+        ] {
+            assert!(css.contains(selector), "{selector} missing");
+        }
+    }
+}
@@ -1,17 +1,31 @@
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
+use serde::{Deserialize, Serialize};
 
-use crate::classes::LayoutClass;
+use crate::classes::{ClassError, LayoutClass};
 use crate::components::Component;
+use crate::diagnostic::{Diagnostic, DiagnosticKind};
 use crate::media_query::MediaQuery;
+use crate::naming::SelectorNames;
 
-#[derive(Hash, PartialEq, Eq, Debug)]
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 pub enum LayoutElement<'a> {
     LayoutComponent(Component<'a>, Option<MediaQuery>),
     LayoutUtility(LayoutClass<'a>, Option<MediaQuery>),
 }
 
 impl<'a> LayoutElement<'a> {
+    /// return the same element owning its values, so it can be kept
+    /// after the parsed text is dropped, like in a cache
+    pub fn into_owned(self) -> LayoutElement<'static> {
+        match self {
+            Self::LayoutComponent(component, mq) => {
+                LayoutElement::LayoutComponent(component.into_owned(), mq)
+            }
+            Self::LayoutUtility(class, mq) => LayoutElement::LayoutUtility(class.into_owned(), mq),
+        }
+    }
+
     /// Generate the css of the layout element
     /// and insert it inside the Hashset passed.
     /// This method consumes the LayoutElement, because we dont need it anymore
@@ -21,21 +35,39 @@ impl<'a> LayoutElement<'a> {
         harmonic_ratio: f64,
         set: &mut HashSet<String>,
         media_queries_rules: &mut HashMap<MediaQuery, HashSet<String>>,
+    ) {
+        self.insert_css_with_names(
+            harmonic_ratio,
+            &SelectorNames::default(),
+            set,
+            media_queries_rules,
+        )
+    }
+
+    /// Same as insert_css, but the css selects the elements with the given names.
+    pub fn insert_css_with_names(
+        self,
+        harmonic_ratio: f64,
+        names: &SelectorNames,
+        set: &mut HashSet<String>,
+        media_queries_rules: &mut HashMap<MediaQuery, HashSet<String>>,
     ) {
         //we can handle here if we should pass the hashset from the css_mq or from css
         //to manage media queries
         match self {
-            Self::LayoutComponent(component, None) => component.insert_css(harmonic_ratio, set),
+            Self::LayoutComponent(component, None) => {
+                component.insert_css_with_names(harmonic_ratio, names, set)
+            }
             //here pass the set from the hashmap
             Self::LayoutComponent(component, Some(mq)) => {
                 let val = media_queries_rules.entry(mq).or_insert_with(HashSet::new);
-                component.insert_css(harmonic_ratio, val);
+                component.insert_css_with_names(harmonic_ratio, names, val);
             }
-            Self::LayoutUtility(class, None) => class.insert_css(harmonic_ratio, set),
+            Self::LayoutUtility(class, None) => class.insert_css_with_names(harmonic_ratio, names, set),
             //here pass the set from the hashmap
             Self::LayoutUtility(class, Some(mq)) => {
                 let val = media_queries_rules.entry(mq).or_insert_with(HashSet::new);
-                class.insert_css(harmonic_ratio, val);
+                class.insert_css_with_names(harmonic_ratio, names, val);
             }
         }
     }
@@ -46,6 +78,7 @@ pub fn generate<'a>(
     layout_attribute: Option<&'a str>,
     media_query: Option<MediaQuery>,
     set: &mut HashSet<LayoutElement<'a>>,
+    diagnostics: &mut Vec<Diagnostic>,
 ) {
     let mut component = Component::from_str(tag_name);
     if let Some(layout_attribute) = layout_attribute {
@@ -59,92 +92,94 @@ pub fn generate<'a>(
                 // here we will handle all LayoutClass attached to a component
                 // to then modify the component accordingly
                 if let Ok(comp) = &mut component {
+                    // set to false when the class is made for another component
+                    let mut applicable = true;
                     match current_class {
                         LayoutClass::MaxWidth(v) => {
                             match comp {
                                 Component::Box { max_width, .. } => *max_width = Some(v),
                                 Component::Center { max_width, .. } => *max_width = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::MinCellWidth(v) => {
                             match comp {
                                 Component::Grid { min_cell_width, .. } => *min_cell_width = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::MinCols(v) => {
                             match comp {
                                 Component::Grid { min_cols, .. } => *min_cols = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::MaxCols(v) => {
                             match comp {
                                 Component::Grid { max_cols, .. } => *max_cols = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Recursive => {
                             match comp {
                                 Component::Stack { recursive, .. } => *recursive = true,
                                 Component::Center { recursive, .. } => *recursive = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Reverse => {
                             match comp {
                                 Component::Switcher { reverse, .. } => *reverse = true,
                                 Component::Sidebar { reverse, .. } => *reverse = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Screen => {
                             match comp {
                                 Component::Extender { screen, .. } => *screen = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::TwinWidth => {
                             match comp {
                                 Component::Row { twin_width, .. } => *twin_width = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::NoWrap => {
                             match comp {
                                 Component::Row { nowrap, .. } => *nowrap = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::HideBar => {
                             match comp {
                                 Component::Slider { hide_bar, .. } => *hide_bar = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Grow => {
                             match comp {
                                 Component::Box { grow, .. } => *grow = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Template(v) => {
                             match comp {
                                 Component::Area { template, .. } => *template = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Row(v) => {
                             match comp {
                                 Component::Area { rows, .. } => rows.push(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Col(v) => {
                             match comp {
                                 Component::Area { cols, .. } => cols.push(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Gap(v) => {
@@ -158,7 +193,7 @@ pub fn generate<'a>(
                                 Component::Slider { gap, .. } => *gap = Some(v),
                                 Component::Stack { gap, .. } => *gap = Some(v),
                                 Component::Switcher { gap, .. } => *gap = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::GapX(v) => {
@@ -168,7 +203,7 @@ pub fn generate<'a>(
                                 Component::Row { gap_x, .. } => *gap_x = Some(v),
                                 Component::Sidebar { gap_x, .. } => *gap_x = Some(v),
                                 Component::Switcher { gap_x, .. } => *gap_x = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::GapY(v) => {
@@ -178,63 +213,69 @@ pub fn generate<'a>(
                                 Component::Row { gap_y, .. } => *gap_y = Some(v),
                                 Component::Sidebar { gap_y, .. } => *gap_y = Some(v),
                                 Component::Switcher { gap_y, .. } => *gap_y = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::GapDir(v) => {
                             match comp {
                                 Component::Icon { gap_dir, .. } => *gap_dir = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Scale(v) => {
                             match comp {
                                 Component::Icon { scale, .. } => *scale = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Align(v) => {
                             match comp {
                                 Component::Icon { align, .. } => *align = Some(v),
                                 Component::Row { align, .. } => *align = Some(v),
-                                _ => {}
+                                _ => applicable = false,
+                            };
+                        }
+                        LayoutClass::Direction(v) => {
+                            match comp {
+                                Component::Row { direction, .. } => *direction = Some(v),
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Justify(v) => {
                             match comp {
                                 Component::Row { justify, .. } => *justify = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
 
                         LayoutClass::Position(v) => {
                             match comp {
                                 Component::Outsider { position, .. } => *position = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Top(v) => {
                             match comp {
                                 Component::Outsider { top, .. } => *top = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Bottom(v) => {
                             match comp {
                                 Component::Outsider { bottom, .. } => *bottom = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Left(v) => {
                             match comp {
                                 Component::Outsider { left, .. } => *left = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Right(v) => {
                             match comp {
                                 Component::Outsider { right, .. } => *right = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
 
@@ -242,114 +283,139 @@ pub fn generate<'a>(
                             match comp {
                                 Component::Rack { height, .. } => *height = Some(v),
                                 Component::Slider { height, .. } => *height = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::ItemWidth(v) => {
                             match comp {
                                 Component::Slider { item_width, .. } => *item_width = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::MinHeight(v) => {
                             match comp {
                                 Component::Rack { min_height, .. } => *min_height = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::MaxHeight(v) => {
                             match comp {
                                 Component::Rack { max_height, .. } => *max_height = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::SideWidth(v) => {
                             match comp {
                                 Component::Sidebar { side_width, .. } => *side_width = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Side(v) => {
                             match comp {
                                 Component::Sidebar { side, .. } => *side = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::ContentMin(v) => {
                             match comp {
                                 Component::Sidebar { content_min, .. } => *content_min = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Threshold(v) => {
                             match comp {
                                 Component::Switcher { threshold, .. } => *threshold = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Limit(v) => {
                             match comp {
                                 Component::Switcher { limit, .. } => *limit = Some(v),
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::KeepP => {
                             match comp {
                                 Component::Extender { keep_p, .. } => *keep_p = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::KeepPL => {
                             match comp {
                                 Component::Extender { keep_pl, .. } => *keep_pl = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::KeepPR => {
                             match comp {
                                 Component::Extender { keep_pr, .. } => *keep_pr = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::KeepCenter => {
                             match comp {
                                 Component::Extender { keep_center, .. } => *keep_center = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::Shrink => {
                             match comp {
                                 Component::Sidebar { shrink, .. } => *shrink = true,
-                                _ => {}
+                                _ => applicable = false,
                             };
                         }
                         LayoutClass::AndText => {
                             match comp {
                                 Component::Center { and_text, .. } => *and_text = true,
-                                _ => {}
+                                _ => applicable = false,
+                            };
+                        }
+                        LayoutClass::Disinherit => {
+                            match comp {
+                                Component::Outsider { .. } => {}
+                                _ => applicable = false,
                             };
                         }
                         _ => {
                             // we dont want to scope utility rules of the component outside the mq
                             // into the mq rules so utilities in the component should be built without mq
-                            let final_mq = match media_query {
-                                Some(MediaQuery::SuperiorTo(_, _)) => None,
-                                Some(MediaQuery::InferiorOrEqualTo(_)) => media_query.clone(),
+                            let final_mq = match &media_query {
+                                Some(mq) if mq.is_breakpoint_attribute() => media_query.clone(),
                                 _ => None,
                             };
                             set.insert(LayoutElement::LayoutUtility(current_class, final_mq));
                         }
                     };
-                } else {
+                    if !applicable {
+                        diagnostics.push(Diagnostic::new(
+                            DiagnosticKind::NotApplicable,
+                            tag_name,
+                            class,
+                        ));
+                    }
+                } else if current_class.is_utility() {
                     // we dont want to scope utility rules of the component outside the mq
                     // into the mq rules so utilities in the component should be built without mq
-                    let final_mq = match media_query {
-                        Some(MediaQuery::SuperiorTo(_, _)) => None,
-                        Some(MediaQuery::InferiorOrEqualTo(_)) => media_query.clone(),
+                    let final_mq = match &media_query {
+                        Some(mq) if mq.is_breakpoint_attribute() => media_query.clone(),
                         _ => None,
                     };
                     set.insert(LayoutElement::LayoutUtility(current_class, final_mq));
+                } else {
+                    // component classes outside a component don't generate anything
+                    diagnostics.push(Diagnostic::new(
+                        DiagnosticKind::NotApplicable,
+                        tag_name,
+                        class,
+                    ));
                 }
+            } else if let Err(error) = layout_class {
+                let kind = match error {
+                    ClassError::Unknown => DiagnosticKind::UnknownClass,
+                    ClassError::MissingValue => DiagnosticKind::MissingValue,
+                    ClassError::InvalidValue => DiagnosticKind::InvalidValue,
+                };
+                diagnostics.push(Diagnostic::new(kind, tag_name, class));
             }
         }
     }
@@ -370,7 +436,7 @@ mod tests {
         let mut mq_set: HashMap<MediaQuery, HashSet<String>> = HashMap::new();
         let el = LayoutElement::LayoutComponent(
             Component::Box {
-                max_width: Some("800px"),
+                max_width: Some("800px".into()),
                 grow: false,
             },
             Some(MediaQuery::SuperiorTo(
@@ -380,7 +446,7 @@ mod tests {
         );
         let el2 = LayoutElement::LayoutComponent(
             Component::Box {
-                max_width: Some("1200px"),
+                max_width: Some("1200px".into()),
                 grow: false,
             },
             Some(MediaQuery::SuperiorTo(
@@ -403,8 +469,31 @@ mod tests {
             Some("max-width:440px max-width:440px grow p:2 p:4 p:2"),
             None,
             &mut set,
+            &mut vec![],
         );
         println!("{:?}oooooooooooo", set);
         assert_eq!(4, 4)
     }
+
+    #[test]
+    fn elements_outlive_the_text_once_owned_or_deserialized() {
+        let text = String::from("gap:2 p:1 template:(a-b)");
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        generate("area-l", Some(&text), None, &mut set, &mut vec![]);
+        let json = serde_json::to_string(&set.iter().collect::<Vec<_>>()).unwrap();
+        let owned: HashSet<LayoutElement<'static>> =
+            set.iter().cloned().map(LayoutElement::into_owned).collect();
+        drop(text);
+
+        let deserialized: HashSet<LayoutElement<'static>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, owned);
+        let css = |elements: HashSet<LayoutElement>| {
+            let (mut rules, mut mq_rules) = (HashSet::new(), HashMap::new());
+            for element in elements {
+                element.insert_css(1.618, &mut rules, &mut mq_rules);
+            }
+            rules
+        };
+        assert_eq!(css(deserialized), css(owned));
+    }
 }
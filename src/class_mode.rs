@@ -0,0 +1,159 @@
+use crate::classes::LayoutClass;
+use crate::components::COMPONENT_TAGS;
+use crate::naming::{ElementNaming, SelectorNames};
+
+/// return the value escaped to be used as a class selector, `p:1.5` gives `p\:1\.5`
+pub fn escape_class(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Rewrite the selectors of the css for the class mode, where the components
+/// and their classes are all written in the class attribute:
+/// `row-l[layout~="gap:2"]` becomes `.row-l.gap\:2`, with the names of the css.
+pub fn to_class_selectors(css: &str, names: &SelectorNames) -> String {
+    let selector_start = format!("[{}", names.attribute);
+    let mut converted = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find(&selector_start) {
+        converted.push_str(&components_to_classes(&rest[..start], names));
+        let after_name = &rest[start + selector_start.len()..];
+        let is_substring = after_name.starts_with("*=");
+        let value = after_name
+            .strip_prefix("~=")
+            .or_else(|| after_name.strip_prefix("*="));
+        match value.zip(value.and_then(|value| value.find(']'))) {
+            Some((value, end)) => {
+                let class = value[..end].trim_matches('"');
+                // `[layout*="bg-img"]` selects every `bg-img:...` class,
+                // it stays a substring selector, on the class attribute
+                if is_substring && !class.contains(':') {
+                    converted.push_str(&format!("[class*=\"{class}:\"]"));
+                } else {
+                    converted.push('.');
+                    converted.push_str(&escape_class(class));
+                }
+                rest = &value[end + 1..];
+            }
+            None => {
+                converted.push_str(&selector_start);
+                rest = after_name;
+            }
+        }
+    }
+    converted.push_str(&components_to_classes(rest, names));
+    converted
+}
+
+/// Return the class read in class mode for a class written in the prefixed form,
+/// `l-gap-2` gives `gap:2` and `l-row` gives `row-l` (in the naming),
+/// None if the class is not in this form.
+pub fn prefixed_class_equivalent(class: &str, naming: &ElementNaming) -> Option<String> {
+    let unprefixed = class.strip_prefix("l-")?;
+    if COMPONENT_TAGS.contains(&format!("{unprefixed}-l").as_str()) {
+        return Some(naming.element_name(unprefixed));
+    }
+    if LayoutClass::try_from(unprefixed).is_ok() {
+        return Some(unprefixed.to_string());
+    }
+    // the value follows one of the dashes, `l-max-width-60ch` is `max-width:60ch`
+    unprefixed
+        .match_indices('-')
+        .map(|(dash, _)| format!("{}:{}", &unprefixed[..dash], &unprefixed[dash + 1..]))
+        .find(|candidate| LayoutClass::try_from(candidate.as_str()).is_ok())
+}
+
+/// replace the component tag names by class selectors, `row-l > *` becomes `.row-l > *`
+fn components_to_classes(css: &str, names: &SelectorNames) -> String {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    let mut converted = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find(is_name_char) {
+        converted.push_str(&rest[..start]);
+        let word_length = rest[start..]
+            .find(|c: char| !is_name_char(c))
+            .unwrap_or(rest.len() - start);
+        let word = &rest[start..start + word_length];
+        // a name after a `.` is already a class
+        if names.element_naming.component_tag(word).is_some() && !rest[..start].ends_with('.') {
+            converted.push('.');
+        }
+        converted.push_str(word);
+        rest = &rest[start + word_length..];
+    }
+    converted.push_str(rest);
+    converted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::naming::ElementNaming;
+
+    #[test]
+    fn values_are_escaped() {
+        assert_eq!(escape_class("p:1.5"), "p\\:1\\.5");
+        assert_eq!(escape_class("max-width:60%"), "max-width\\:60\\%");
+    }
+
+    #[test]
+    fn attribute_selectors_become_classes() {
+        assert_eq!(
+            to_class_selectors(
+                "sidebar-l[layout*=\"side:left\"] > :first-child:not(outsider-l[layout~=\"disinherit\"]){}",
+                &SelectorNames::default()
+            ),
+            ".sidebar-l.side\\:left > :first-child:not(.outsider-l.disinherit){}"
+        );
+        let names = SelectorNames {
+            attribute: "data-l".to_string(),
+            element_naming: ElementNaming::new("lc-{name}").unwrap(),
+        };
+        assert_eq!(to_class_selectors("[data-l~=\"p:2\"]{}", &names), ".p\\:2{}");
+        assert_eq!(
+            to_class_selectors("lc-row > row-l[data-l~=\"nowrap\"]{}", &names),
+            ".lc-row > row-l.nowrap{}"
+        );
+        assert_eq!(
+            to_class_selectors("sidebar-l[layout~=shrink]{}", &SelectorNames::default()),
+            ".sidebar-l.shrink{}"
+        );
+    }
+
+    #[test]
+    fn prefixed_classes_are_recognized() {
+        let naming = ElementNaming::default();
+        assert_eq!(prefixed_class_equivalent("l-row", &naming), Some("row-l".to_string()));
+        assert_eq!(prefixed_class_equivalent("l-gap-2", &naming), Some("gap:2".to_string()));
+        assert_eq!(
+            prefixed_class_equivalent("l-max-width-60ch", &naming),
+            Some("max-width:60ch".to_string())
+        );
+        assert_eq!(prefixed_class_equivalent("l-p-child-1", &naming), Some("p-child:1".to_string()));
+        assert_eq!(prefixed_class_equivalent("l-nowrap", &naming), Some("nowrap".to_string()));
+        assert_eq!(
+            prefixed_class_equivalent("l-row", &ElementNaming::new("lc-{name}").unwrap()),
+            Some("lc-row".to_string())
+        );
+        assert_eq!(prefixed_class_equivalent("l-card", &naming), None);
+        assert_eq!(prefixed_class_equivalent("gap-2", &naming), None);
+    }
+
+    #[test]
+    fn substring_selectors_of_a_class_name_stay_substring_selectors() {
+        assert_eq!(
+            to_class_selectors("[layout*=\"bg-img\"]{}", &SelectorNames::default()),
+            "[class*=\"bg-img:\"]{}"
+        );
+        assert_eq!(
+            to_class_selectors("icon-l[layout*=\"gap-dir:end\"]{}", &SelectorNames::default()),
+            ".icon-l.gap-dir\\:end{}"
+        );
+    }
+}
@@ -1,7 +1,11 @@
-use std::{collections::HashSet, hash::Hash};
+use std::{borrow::Cow, collections::HashSet, hash::Hash};
+use serde::{Deserialize, Serialize};
 
+use crate::harmonic::is_harmonic_value;
+use crate::naming::SelectorNames;
 use crate::utilities::align_self::align_self_css;
 use crate::utilities::bg_img::bg_img_css;
+use crate::utilities::container::container_css;
 use crate::utilities::flex::*;
 use crate::utilities::font_size::font_size_css;
 use crate::utilities::h::h_css;
@@ -15,13 +19,13 @@ use crate::utilities::relative::relative_css;
 use crate::utilities::w::w_css;
 use crate::utilities::z_index::z_index_css;
 
-#[derive(Debug, PartialEq, Hash, Eq)]
+#[derive(Debug, PartialEq, Hash, Eq, Clone, Serialize, Deserialize)]
 pub enum LayoutClass<'a> {
     // Component Classes
-    MaxWidth(&'a str),
-    MinCellWidth(&'a str),
-    MinCols(&'a str),
-    MaxCols(&'a str),
+    MaxWidth(Cow<'a, str>),
+    MinCellWidth(Cow<'a, str>),
+    MinCols(Cow<'a, str>),
+    MaxCols(Cow<'a, str>),
     Recursive,
     Reverse,
     Screen,
@@ -30,76 +34,103 @@ pub enum LayoutClass<'a> {
     HideBar,
     AndText,
     Grow,
-    Gap(&'a str),
-    GapX(&'a str),
-    GapY(&'a str),
-    GapDir(&'a str),
-    Scale(&'a str),
-    Align(&'a str),
-    Position(&'a str),
-    Top(&'a str),
-    Bottom(&'a str),
-    Left(&'a str),
-    Right(&'a str),
-    Height(&'a str),
-    ItemWidth(&'a str),
-    MinHeight(&'a str),
-    MaxHeight(&'a str),
-    SideWidth(&'a str),
-    Side(&'a str),
-    ContentMin(&'a str),
-    Threshold(&'a str),
-    Limit(&'a str),
+    Gap(Cow<'a, str>),
+    GapX(Cow<'a, str>),
+    GapY(Cow<'a, str>),
+    GapDir(Cow<'a, str>),
+    Scale(Cow<'a, str>),
+    Align(Cow<'a, str>),
+    Position(Cow<'a, str>),
+    Top(Cow<'a, str>),
+    Bottom(Cow<'a, str>),
+    Left(Cow<'a, str>),
+    Right(Cow<'a, str>),
+    Height(Cow<'a, str>),
+    ItemWidth(Cow<'a, str>),
+    MinHeight(Cow<'a, str>),
+    MaxHeight(Cow<'a, str>),
+    SideWidth(Cow<'a, str>),
+    Side(Cow<'a, str>),
+    ContentMin(Cow<'a, str>),
+    Threshold(Cow<'a, str>),
+    Limit(Cow<'a, str>),
     KeepP,
     KeepPL,
     KeepPR,
     KeepCenter,
     Shrink,
-    Template(&'a str),
-    Col(&'a str),
-    Row(&'a str),
+    Template(Cow<'a, str>),
+    Col(Cow<'a, str>),
+    Row(Cow<'a, str>),
+    Disinherit,
+    Direction(Cow<'a, str>),
 
     // Utility Classes
-    AlignSelf(&'a str),
-    BgImg(&'a str),
-    FlexBasis(&'a str),
-    FlexGrow(&'a str),
-    FlexShrink(&'a str),
-    FontSize(&'a str),
-    H(&'a str),
-    HideOver(&'a str),
-    HideUnder(&'a str),
-    LineHeight(&'a str),
-    Justify(&'a str),
-    P(&'a str),
-    PT(&'a str),
-    PB(&'a str),
-    PL(&'a str),
-    PR(&'a str),
-    PX(&'a str),
-    PY(&'a str),
-    PChild(&'a str),
-    PTChild(&'a str),
-    PBChild(&'a str),
-    PLChild(&'a str),
-    PRChild(&'a str),
-    PXChild(&'a str),
-    PYChild(&'a str),
-    PRecursive(&'a str),
-    PTRecursive(&'a str),
-    PBRecursive(&'a str),
-    PLRecursive(&'a str),
-    PRRecursive(&'a str),
-    PXRecursive(&'a str),
-    PYRecursive(&'a str),
-    Ratio(&'a str),
+    AlignSelf(Cow<'a, str>),
+    BgImg(Cow<'a, str>),
+    Container,
+    FlexBasis(Cow<'a, str>),
+    FlexGrow(Cow<'a, str>),
+    FlexShrink(Cow<'a, str>),
+    FontSize(Cow<'a, str>),
+    H(Cow<'a, str>),
+    HideOver(Cow<'a, str>),
+    HideUnder(Cow<'a, str>),
+    LineHeight(Cow<'a, str>),
+    Justify(Cow<'a, str>),
+    P(Cow<'a, str>),
+    PT(Cow<'a, str>),
+    PB(Cow<'a, str>),
+    PL(Cow<'a, str>),
+    PR(Cow<'a, str>),
+    PX(Cow<'a, str>),
+    PY(Cow<'a, str>),
+    PChild(Cow<'a, str>),
+    PTChild(Cow<'a, str>),
+    PBChild(Cow<'a, str>),
+    PLChild(Cow<'a, str>),
+    PRChild(Cow<'a, str>),
+    PXChild(Cow<'a, str>),
+    PYChild(Cow<'a, str>),
+    PRecursive(Cow<'a, str>),
+    PTRecursive(Cow<'a, str>),
+    PBRecursive(Cow<'a, str>),
+    PLRecursive(Cow<'a, str>),
+    PRRecursive(Cow<'a, str>),
+    PXRecursive(Cow<'a, str>),
+    PYRecursive(Cow<'a, str>),
+    Ratio(Cow<'a, str>),
     Relative,
-    W(&'a str),
-    ZIndex(&'a str),
+    W(Cow<'a, str>),
+    ZIndex(Cow<'a, str>),
+    // Marker used by the rules of the parent component (rack-l)
+    Centered,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ClassError {
+    /// The class name doesn't exist.
+    Unknown,
+    /// The class name exists but its value is missing, like `max-width:` or `max-width`.
+    MissingValue,
+    /// The class takes a value of the harmonic scale but got something else, like `gap:2x`.
+    InvalidValue,
+}
+
+/// Classes whose value is a number of the harmonic scale or a css length.
+const HARMONIC_CLASSES: [&str; 24] = [
+    "gap", "gap-x", "gap-y", "p", "pt", "pb", "pl", "pr", "px", "py", "p-child", "pt-child",
+    "pb-child", "pl-child", "pr-child", "px-child", "py-child", "p-recursive", "pt-recursive",
+    "pb-recursive", "pl-recursive", "pr-recursive", "px-recursive", "py-recursive",
+];
+
+/// return true if the class name exists but only with a value, like `max-width`
+fn expects_value(class_name: &str) -> bool {
+    LayoutClass::try_from(format!("{class_name}:0").as_str()).is_ok()
 }
 
 impl<'a> TryFrom<&'a str> for LayoutClass<'a> {
-    type Error = ();
+    type Error = ClassError;
 
     fn try_from(input: &'a str) -> Result<LayoutClass<'a>, Self::Error> {
         let colon_index = input.find(':');
@@ -107,85 +138,95 @@ impl<'a> TryFrom<&'a str> for LayoutClass<'a> {
             //In the case where their is ':' but nothing after
             // this syntax is wrong so we return an error
             if input.len() <= i + 1 {
-                return Err(());
+                return if expects_value(&input[..i]) {
+                    Err(ClassError::MissingValue)
+                } else {
+                    Err(ClassError::Unknown)
+                };
             }
             (Some(&input[..i]), Some(&input[i + 1..]))
         } else {
             (Some(&input[..]), None)
         };
         match (class_name, class_value) {
+            (Some(class), Some(value))
+                if HARMONIC_CLASSES.contains(&class) && !is_harmonic_value(value) =>
+            {
+                Err(ClassError::InvalidValue)
+            }
             // all layout classes with value
             (Some(class), Some(value)) => match class {
-                "max-width" => Ok(LayoutClass::MaxWidth(value)),
-                "min-cell-width" => Ok(LayoutClass::MinCellWidth(value)),
-                "min-cols" => Ok(LayoutClass::MinCols(value)),
-                "max-cols" => Ok(LayoutClass::MaxCols(value)),
-                "gap" => Ok(LayoutClass::Gap(value)),
-                "gap-x" => Ok(LayoutClass::GapX(value)),
-                "gap-y" => Ok(LayoutClass::GapY(value)),
-                "gap-dir" => Ok(LayoutClass::GapDir(value)),
-                "scale" => Ok(LayoutClass::Scale(value)),
-                "align" => Ok(LayoutClass::Align(value)),
-                "position" => Ok(LayoutClass::Position(value)),
-                "top" => Ok(LayoutClass::Top(value)),
-                "bottom" => Ok(LayoutClass::Bottom(value)),
-                "left" => Ok(LayoutClass::Left(value)),
-                "right" => Ok(LayoutClass::Right(value)),
-                "height" => Ok(LayoutClass::Height(value)),
-                "item-width" => Ok(LayoutClass::ItemWidth(value)),
-                "min-height" => Ok(LayoutClass::MinHeight(value)),
-                "max-height" => Ok(LayoutClass::MaxHeight(value)),
-                "side" => Ok(LayoutClass::Side(value)),
-                "side-width" => Ok(LayoutClass::SideWidth(value)),
-                "content-min" => Ok(LayoutClass::ContentMin(value)),
-                "threshold" => Ok(LayoutClass::Threshold(value)),
-                "template" => Ok(LayoutClass::Template(value)),
-                "limit" => Ok(LayoutClass::Limit(value)),
+                "max-width" => Ok(LayoutClass::MaxWidth(value.into())),
+                "min-cell-width" => Ok(LayoutClass::MinCellWidth(value.into())),
+                "min-cols" => Ok(LayoutClass::MinCols(value.into())),
+                "max-cols" => Ok(LayoutClass::MaxCols(value.into())),
+                "gap" => Ok(LayoutClass::Gap(value.into())),
+                "gap-x" => Ok(LayoutClass::GapX(value.into())),
+                "gap-y" => Ok(LayoutClass::GapY(value.into())),
+                "gap-dir" => Ok(LayoutClass::GapDir(value.into())),
+                "scale" => Ok(LayoutClass::Scale(value.into())),
+                "align" => Ok(LayoutClass::Align(value.into())),
+                "position" => Ok(LayoutClass::Position(value.into())),
+                "top" => Ok(LayoutClass::Top(value.into())),
+                "bottom" => Ok(LayoutClass::Bottom(value.into())),
+                "left" => Ok(LayoutClass::Left(value.into())),
+                "right" => Ok(LayoutClass::Right(value.into())),
+                "height" => Ok(LayoutClass::Height(value.into())),
+                "item-width" => Ok(LayoutClass::ItemWidth(value.into())),
+                "min-height" => Ok(LayoutClass::MinHeight(value.into())),
+                "max-height" => Ok(LayoutClass::MaxHeight(value.into())),
+                "side" => Ok(LayoutClass::Side(value.into())),
+                "side-width" => Ok(LayoutClass::SideWidth(value.into())),
+                "content-min" => Ok(LayoutClass::ContentMin(value.into())),
+                "threshold" => Ok(LayoutClass::Threshold(value.into())),
+                "template" => Ok(LayoutClass::Template(value.into())),
+                "limit" => Ok(LayoutClass::Limit(value.into())),
+                "direction" => Ok(LayoutClass::Direction(value.into())),
 
-                "align-self" => Ok(LayoutClass::AlignSelf(value)),
-                "bg-img" => Ok(LayoutClass::BgImg(value)),
-                "flex-basis" => Ok(LayoutClass::FlexBasis(value)),
-                "flex-grow" => Ok(LayoutClass::FlexGrow(value)),
-                "flex-shrink" => Ok(LayoutClass::FlexShrink(value)),
-                "font-size" => Ok(LayoutClass::FontSize(value)),
-                "h" => Ok(LayoutClass::H(value)),
-                "hide-over" => Ok(LayoutClass::HideOver(value)),
-                "hide-under" => Ok(LayoutClass::HideUnder(value)),
-                "line-height" => Ok(LayoutClass::LineHeight(value)),
-                "p" => Ok(LayoutClass::P(value)),
-                "pt" => Ok(LayoutClass::PT(value)),
-                "pb" => Ok(LayoutClass::PB(value)),
-                "pl" => Ok(LayoutClass::PL(value)),
-                "pr" => Ok(LayoutClass::PR(value)),
-                "px" => Ok(LayoutClass::PX(value)),
-                "py" => Ok(LayoutClass::PY(value)),
-                "p-child" => Ok(LayoutClass::PChild(value)),
-                "pt-child" => Ok(LayoutClass::PTChild(value)),
-                "pb-child" => Ok(LayoutClass::PBChild(value)),
-                "pl-child" => Ok(LayoutClass::PLChild(value)),
-                "pr-child" => Ok(LayoutClass::PRChild(value)),
-                "px-child" => Ok(LayoutClass::PXChild(value)),
-                "py-child" => Ok(LayoutClass::PYChild(value)),
-                "p-recursive" => Ok(LayoutClass::PRecursive(value)),
-                "pt-recursive" => Ok(LayoutClass::PTRecursive(value)),
-                "pb-recursive" => Ok(LayoutClass::PBRecursive(value)),
-                "pl-recursive" => Ok(LayoutClass::PLRecursive(value)),
-                "pr-recursive" => Ok(LayoutClass::PRRecursive(value)),
-                "px-recursive" => Ok(LayoutClass::PXRecursive(value)),
-                "py-recursive" => Ok(LayoutClass::PYRecursive(value)),
+                "align-self" => Ok(LayoutClass::AlignSelf(value.into())),
+                "bg-img" => Ok(LayoutClass::BgImg(value.into())),
+                "flex-basis" => Ok(LayoutClass::FlexBasis(value.into())),
+                "flex-grow" => Ok(LayoutClass::FlexGrow(value.into())),
+                "flex-shrink" => Ok(LayoutClass::FlexShrink(value.into())),
+                "font-size" => Ok(LayoutClass::FontSize(value.into())),
+                "h" => Ok(LayoutClass::H(value.into())),
+                "hide-over" => Ok(LayoutClass::HideOver(value.into())),
+                "hide-under" => Ok(LayoutClass::HideUnder(value.into())),
+                "line-height" => Ok(LayoutClass::LineHeight(value.into())),
+                "p" => Ok(LayoutClass::P(value.into())),
+                "pt" => Ok(LayoutClass::PT(value.into())),
+                "pb" => Ok(LayoutClass::PB(value.into())),
+                "pl" => Ok(LayoutClass::PL(value.into())),
+                "pr" => Ok(LayoutClass::PR(value.into())),
+                "px" => Ok(LayoutClass::PX(value.into())),
+                "py" => Ok(LayoutClass::PY(value.into())),
+                "p-child" => Ok(LayoutClass::PChild(value.into())),
+                "pt-child" => Ok(LayoutClass::PTChild(value.into())),
+                "pb-child" => Ok(LayoutClass::PBChild(value.into())),
+                "pl-child" => Ok(LayoutClass::PLChild(value.into())),
+                "pr-child" => Ok(LayoutClass::PRChild(value.into())),
+                "px-child" => Ok(LayoutClass::PXChild(value.into())),
+                "py-child" => Ok(LayoutClass::PYChild(value.into())),
+                "p-recursive" => Ok(LayoutClass::PRecursive(value.into())),
+                "pt-recursive" => Ok(LayoutClass::PTRecursive(value.into())),
+                "pb-recursive" => Ok(LayoutClass::PBRecursive(value.into())),
+                "pl-recursive" => Ok(LayoutClass::PLRecursive(value.into())),
+                "pr-recursive" => Ok(LayoutClass::PRRecursive(value.into())),
+                "px-recursive" => Ok(LayoutClass::PXRecursive(value.into())),
+                "py-recursive" => Ok(LayoutClass::PYRecursive(value.into())),
 
-                "justify" => Ok(LayoutClass::Justify(value)),
+                "justify" => Ok(LayoutClass::Justify(value.into())),
 
-                "ratio" => Ok(LayoutClass::Ratio(value)),
-                "w" => Ok(LayoutClass::W(value)),
-                "z-index" => Ok(LayoutClass::ZIndex(value)),
+                "ratio" => Ok(LayoutClass::Ratio(value.into())),
+                "w" => Ok(LayoutClass::W(value.into())),
+                "z-index" => Ok(LayoutClass::ZIndex(value.into())),
 
                 //TODO check if its not row instead of rows
                 //if its the case rename Rows Row (same thing for Cols to Col)
-                c if c.starts_with("row-") => Ok(LayoutClass::Row(input)),
-                c if c.starts_with("col-") => Ok(LayoutClass::Col(input)),
+                c if c.starts_with("row-") => Ok(LayoutClass::Row(input.into())),
+                c if c.starts_with("col-") => Ok(LayoutClass::Col(input.into())),
 
-                _ => Err(()),
+                _ => Err(ClassError::Unknown),
             },
             // all layout class without value
             (Some(class), None) => match class {
@@ -196,6 +237,7 @@ impl<'a> TryFrom<&'a str> for LayoutClass<'a> {
                 "nowrap" => Ok(LayoutClass::NoWrap),
                 "hide-bar" => Ok(LayoutClass::HideBar),
                 "relative" => Ok(LayoutClass::Relative),
+                "container" => Ok(LayoutClass::Container),
                 "grow" => Ok(LayoutClass::Grow),
                 "keep-p" => Ok(LayoutClass::KeepP),
                 "keep-pl" => Ok(LayoutClass::KeepPL),
@@ -203,55 +245,200 @@ impl<'a> TryFrom<&'a str> for LayoutClass<'a> {
                 "keep-center" => Ok(LayoutClass::KeepCenter),
                 "shrink" => Ok(LayoutClass::Shrink),
                 "and-text" => Ok(LayoutClass::AndText),
-                _ => Err(()),
+                "disinherit" => Ok(LayoutClass::Disinherit),
+                "centered" => Ok(LayoutClass::Centered),
+                c if expects_value(c) => Err(ClassError::MissingValue),
+                _ => Err(ClassError::Unknown),
             },
-            _ => Err(()),
+            _ => Err(ClassError::Unknown),
         }
     }
 }
 
 impl<'a> LayoutClass<'a> {
+    /// return true if the class can be used on any element,
+    /// and false if it only makes sense on a component.
+    pub fn is_utility(&self) -> bool {
+        matches!(
+            self,
+            Self::AlignSelf(_)
+                | Self::BgImg(_)
+                | Self::Container
+                | Self::FlexBasis(_)
+                | Self::FlexGrow(_)
+                | Self::FlexShrink(_)
+                | Self::FontSize(_)
+                | Self::H(_)
+                | Self::HideOver(_)
+                | Self::HideUnder(_)
+                | Self::LineHeight(_)
+                | Self::P(_)
+                | Self::PT(_)
+                | Self::PB(_)
+                | Self::PL(_)
+                | Self::PR(_)
+                | Self::PX(_)
+                | Self::PY(_)
+                | Self::PChild(_)
+                | Self::PTChild(_)
+                | Self::PBChild(_)
+                | Self::PLChild(_)
+                | Self::PRChild(_)
+                | Self::PXChild(_)
+                | Self::PYChild(_)
+                | Self::PRecursive(_)
+                | Self::PTRecursive(_)
+                | Self::PBRecursive(_)
+                | Self::PLRecursive(_)
+                | Self::PRRecursive(_)
+                | Self::PXRecursive(_)
+                | Self::PYRecursive(_)
+                | Self::Ratio(_)
+                | Self::Relative
+                | Self::W(_)
+                | Self::ZIndex(_)
+                | Self::Centered
+        )
+    }
+
+    /// return the same class owning its value, so it can outlive the parsed text
+    pub fn into_owned(self) -> LayoutClass<'static> {
+        match self {
+            Self::MaxWidth(value) => LayoutClass::MaxWidth(Cow::Owned(value.into_owned())),
+            Self::MinCellWidth(value) => LayoutClass::MinCellWidth(Cow::Owned(value.into_owned())),
+            Self::MinCols(value) => LayoutClass::MinCols(Cow::Owned(value.into_owned())),
+            Self::MaxCols(value) => LayoutClass::MaxCols(Cow::Owned(value.into_owned())),
+            Self::Recursive => LayoutClass::Recursive,
+            Self::Reverse => LayoutClass::Reverse,
+            Self::Screen => LayoutClass::Screen,
+            Self::TwinWidth => LayoutClass::TwinWidth,
+            Self::NoWrap => LayoutClass::NoWrap,
+            Self::HideBar => LayoutClass::HideBar,
+            Self::AndText => LayoutClass::AndText,
+            Self::Grow => LayoutClass::Grow,
+            Self::Gap(value) => LayoutClass::Gap(Cow::Owned(value.into_owned())),
+            Self::GapX(value) => LayoutClass::GapX(Cow::Owned(value.into_owned())),
+            Self::GapY(value) => LayoutClass::GapY(Cow::Owned(value.into_owned())),
+            Self::GapDir(value) => LayoutClass::GapDir(Cow::Owned(value.into_owned())),
+            Self::Scale(value) => LayoutClass::Scale(Cow::Owned(value.into_owned())),
+            Self::Align(value) => LayoutClass::Align(Cow::Owned(value.into_owned())),
+            Self::Position(value) => LayoutClass::Position(Cow::Owned(value.into_owned())),
+            Self::Top(value) => LayoutClass::Top(Cow::Owned(value.into_owned())),
+            Self::Bottom(value) => LayoutClass::Bottom(Cow::Owned(value.into_owned())),
+            Self::Left(value) => LayoutClass::Left(Cow::Owned(value.into_owned())),
+            Self::Right(value) => LayoutClass::Right(Cow::Owned(value.into_owned())),
+            Self::Height(value) => LayoutClass::Height(Cow::Owned(value.into_owned())),
+            Self::ItemWidth(value) => LayoutClass::ItemWidth(Cow::Owned(value.into_owned())),
+            Self::MinHeight(value) => LayoutClass::MinHeight(Cow::Owned(value.into_owned())),
+            Self::MaxHeight(value) => LayoutClass::MaxHeight(Cow::Owned(value.into_owned())),
+            Self::SideWidth(value) => LayoutClass::SideWidth(Cow::Owned(value.into_owned())),
+            Self::Side(value) => LayoutClass::Side(Cow::Owned(value.into_owned())),
+            Self::ContentMin(value) => LayoutClass::ContentMin(Cow::Owned(value.into_owned())),
+            Self::Threshold(value) => LayoutClass::Threshold(Cow::Owned(value.into_owned())),
+            Self::Limit(value) => LayoutClass::Limit(Cow::Owned(value.into_owned())),
+            Self::KeepP => LayoutClass::KeepP,
+            Self::KeepPL => LayoutClass::KeepPL,
+            Self::KeepPR => LayoutClass::KeepPR,
+            Self::KeepCenter => LayoutClass::KeepCenter,
+            Self::Shrink => LayoutClass::Shrink,
+            Self::Template(value) => LayoutClass::Template(Cow::Owned(value.into_owned())),
+            Self::Col(value) => LayoutClass::Col(Cow::Owned(value.into_owned())),
+            Self::Row(value) => LayoutClass::Row(Cow::Owned(value.into_owned())),
+            Self::Disinherit => LayoutClass::Disinherit,
+            Self::Direction(value) => LayoutClass::Direction(Cow::Owned(value.into_owned())),
+            Self::AlignSelf(value) => LayoutClass::AlignSelf(Cow::Owned(value.into_owned())),
+            Self::BgImg(value) => LayoutClass::BgImg(Cow::Owned(value.into_owned())),
+            Self::Container => LayoutClass::Container,
+            Self::FlexBasis(value) => LayoutClass::FlexBasis(Cow::Owned(value.into_owned())),
+            Self::FlexGrow(value) => LayoutClass::FlexGrow(Cow::Owned(value.into_owned())),
+            Self::FlexShrink(value) => LayoutClass::FlexShrink(Cow::Owned(value.into_owned())),
+            Self::FontSize(value) => LayoutClass::FontSize(Cow::Owned(value.into_owned())),
+            Self::H(value) => LayoutClass::H(Cow::Owned(value.into_owned())),
+            Self::HideOver(value) => LayoutClass::HideOver(Cow::Owned(value.into_owned())),
+            Self::HideUnder(value) => LayoutClass::HideUnder(Cow::Owned(value.into_owned())),
+            Self::LineHeight(value) => LayoutClass::LineHeight(Cow::Owned(value.into_owned())),
+            Self::Justify(value) => LayoutClass::Justify(Cow::Owned(value.into_owned())),
+            Self::P(value) => LayoutClass::P(Cow::Owned(value.into_owned())),
+            Self::PT(value) => LayoutClass::PT(Cow::Owned(value.into_owned())),
+            Self::PB(value) => LayoutClass::PB(Cow::Owned(value.into_owned())),
+            Self::PL(value) => LayoutClass::PL(Cow::Owned(value.into_owned())),
+            Self::PR(value) => LayoutClass::PR(Cow::Owned(value.into_owned())),
+            Self::PX(value) => LayoutClass::PX(Cow::Owned(value.into_owned())),
+            Self::PY(value) => LayoutClass::PY(Cow::Owned(value.into_owned())),
+            Self::PChild(value) => LayoutClass::PChild(Cow::Owned(value.into_owned())),
+            Self::PTChild(value) => LayoutClass::PTChild(Cow::Owned(value.into_owned())),
+            Self::PBChild(value) => LayoutClass::PBChild(Cow::Owned(value.into_owned())),
+            Self::PLChild(value) => LayoutClass::PLChild(Cow::Owned(value.into_owned())),
+            Self::PRChild(value) => LayoutClass::PRChild(Cow::Owned(value.into_owned())),
+            Self::PXChild(value) => LayoutClass::PXChild(Cow::Owned(value.into_owned())),
+            Self::PYChild(value) => LayoutClass::PYChild(Cow::Owned(value.into_owned())),
+            Self::PRecursive(value) => LayoutClass::PRecursive(Cow::Owned(value.into_owned())),
+            Self::PTRecursive(value) => LayoutClass::PTRecursive(Cow::Owned(value.into_owned())),
+            Self::PBRecursive(value) => LayoutClass::PBRecursive(Cow::Owned(value.into_owned())),
+            Self::PLRecursive(value) => LayoutClass::PLRecursive(Cow::Owned(value.into_owned())),
+            Self::PRRecursive(value) => LayoutClass::PRRecursive(Cow::Owned(value.into_owned())),
+            Self::PXRecursive(value) => LayoutClass::PXRecursive(Cow::Owned(value.into_owned())),
+            Self::PYRecursive(value) => LayoutClass::PYRecursive(Cow::Owned(value.into_owned())),
+            Self::Ratio(value) => LayoutClass::Ratio(Cow::Owned(value.into_owned())),
+            Self::Relative => LayoutClass::Relative,
+            Self::W(value) => LayoutClass::W(Cow::Owned(value.into_owned())),
+            Self::ZIndex(value) => LayoutClass::ZIndex(Cow::Owned(value.into_owned())),
+            Self::Centered => LayoutClass::Centered,
+        }
+    }
+
     /// Generate the css of the layout class which are utilities
     /// and insert it inside the Hashset passed.
     /// This method consumes the LayoutClass, because we dont need it anymore
     /// after we get the css.
     pub fn insert_css(self, harmonic_ratio: f64, set: &mut HashSet<String>) {
+        self.insert_css_with_names(harmonic_ratio, &SelectorNames::default(), set)
+    }
+
+    /// Same as insert_css, but the css selects the elements with the given names.
+    pub fn insert_css_with_names(
+        self,
+        harmonic_ratio: f64,
+        names: &SelectorNames,
+        set: &mut HashSet<String>,
+    ) {
         match self {
-            Self::AlignSelf(value) => align_self_css(value, set),
-            Self::BgImg(value) => bg_img_css(value, set),
-            Self::FlexBasis(value) => flex_basis_css(value, set),
-            Self::FlexGrow(value) => flex_grow_css(value, set),
-            Self::FlexShrink(value) => flex_shrink_css(value, set),
-            Self::FontSize(value) => font_size_css(value, harmonic_ratio, set),
-            Self::H(value) => h_css(value, harmonic_ratio, set),
-            Self::HideOver(value) => hide_over_css(value, set),
-            Self::HideUnder(value) => hide_under_css(value, set),
-            Self::LineHeight(value) => line_height_css(value, set),
-            Self::P(value) => p_css(value, harmonic_ratio, set),
-            Self::PT(value) => pt_css(value, harmonic_ratio, set),
-            Self::PB(value) => pb_css(value, harmonic_ratio, set),
-            Self::PL(value) => pl_css(value, harmonic_ratio, set),
-            Self::PR(value) => pr_css(value, harmonic_ratio, set),
-            Self::PX(value) => px_css(value, harmonic_ratio, set),
-            Self::PY(value) => py_css(value, harmonic_ratio, set),
-            Self::PChild(value) => p_child_css(value, harmonic_ratio, set),
-            Self::PTChild(value) => pt_child_css(value, harmonic_ratio, set),
-            Self::PBChild(value) => pb_child_css(value, harmonic_ratio, set),
-            Self::PLChild(value) => pl_child_css(value, harmonic_ratio, set),
-            Self::PRChild(value) => pr_child_css(value, harmonic_ratio, set),
-            Self::PXChild(value) => px_child_css(value, harmonic_ratio, set),
-            Self::PYChild(value) => py_child_css(value, harmonic_ratio, set),
-            Self::PRecursive(value) => p_recursive_css(value, harmonic_ratio, set),
-            Self::PTRecursive(value) => pt_recursive_css(value, harmonic_ratio, set),
-            Self::PBRecursive(value) => pb_recursive_css(value, harmonic_ratio, set),
-            Self::PLRecursive(value) => pl_recursive_css(value, harmonic_ratio, set),
-            Self::PRRecursive(value) => pr_recursive_css(value, harmonic_ratio, set),
-            Self::PXRecursive(value) => px_recursive_css(value, harmonic_ratio, set),
-            Self::PYRecursive(value) => py_recursive_css(value, harmonic_ratio, set),
-            Self::Ratio(value) => ratio_css(value, set),
-            Self::Relative => relative_css(set),
-            Self::W(value) => w_css(value, harmonic_ratio, set),
-            Self::ZIndex(value) => z_index_css(value, set),
+            Self::AlignSelf(value) => align_self_css(&value, names, set),
+            Self::BgImg(value) => bg_img_css(&value, names, set),
+            Self::Container => container_css(names, set),
+            Self::FlexBasis(value) => flex_basis_css(&value, names, set),
+            Self::FlexGrow(value) => flex_grow_css(&value, names, set),
+            Self::FlexShrink(value) => flex_shrink_css(&value, names, set),
+            Self::FontSize(value) => font_size_css(&value, harmonic_ratio, names, set),
+            Self::H(value) => h_css(&value, harmonic_ratio, names, set),
+            Self::HideOver(value) => hide_over_css(&value, names, set),
+            Self::HideUnder(value) => hide_under_css(&value, names, set),
+            Self::LineHeight(value) => line_height_css(&value, names, set),
+            Self::P(value) => p_css(&value, harmonic_ratio, names, set),
+            Self::PT(value) => pt_css(&value, harmonic_ratio, names, set),
+            Self::PB(value) => pb_css(&value, harmonic_ratio, names, set),
+            Self::PL(value) => pl_css(&value, harmonic_ratio, names, set),
+            Self::PR(value) => pr_css(&value, harmonic_ratio, names, set),
+            Self::PX(value) => px_css(&value, harmonic_ratio, names, set),
+            Self::PY(value) => py_css(&value, harmonic_ratio, names, set),
+            Self::PChild(value) => p_child_css(&value, harmonic_ratio, names, set),
+            Self::PTChild(value) => pt_child_css(&value, harmonic_ratio, names, set),
+            Self::PBChild(value) => pb_child_css(&value, harmonic_ratio, names, set),
+            Self::PLChild(value) => pl_child_css(&value, harmonic_ratio, names, set),
+            Self::PRChild(value) => pr_child_css(&value, harmonic_ratio, names, set),
+            Self::PXChild(value) => px_child_css(&value, harmonic_ratio, names, set),
+            Self::PYChild(value) => py_child_css(&value, harmonic_ratio, names, set),
+            Self::PRecursive(value) => p_recursive_css(&value, harmonic_ratio, names, set),
+            Self::PTRecursive(value) => pt_recursive_css(&value, harmonic_ratio, names, set),
+            Self::PBRecursive(value) => pb_recursive_css(&value, harmonic_ratio, names, set),
+            Self::PLRecursive(value) => pl_recursive_css(&value, harmonic_ratio, names, set),
+            Self::PRRecursive(value) => pr_recursive_css(&value, harmonic_ratio, names, set),
+            Self::PXRecursive(value) => px_recursive_css(&value, harmonic_ratio, names, set),
+            Self::PYRecursive(value) => py_recursive_css(&value, harmonic_ratio, names, set),
+            Self::Ratio(value) => ratio_css(&value, names, set),
+            Self::Relative => relative_css(names, set),
+            Self::W(value) => w_css(&value, harmonic_ratio, names, set),
+            Self::ZIndex(value) => z_index_css(&value, names, set),
             _ => {}
         }
     }
@@ -266,27 +453,38 @@ mod tests {
         let layout_class = "col-3:440px";
         let cols_variant = LayoutClass::try_from(layout_class);
         //assert_eq!(cols_variant, LayoutClass::Cols("cols-"));
-        assert_eq!(cols_variant, Ok(LayoutClass::Col("col-3:440px")));
+        assert_eq!(cols_variant, Ok(LayoutClass::Col("col-3:440px".into())));
     }
 
     #[test]
     fn create_layout_class_when_value_expected_but_no_set_with_colon() {
         let layout_class = "max-width:";
         let max_width_variant = LayoutClass::try_from(layout_class);
-        assert_eq!(max_width_variant, Err(()));
+        assert_eq!(max_width_variant, Err(ClassError::MissingValue));
     }
 
     #[test]
     fn create_layout_class_when_value_expected_but_no_set_without_colon() {
         let layout_class = "max-width";
         let max_width_variant = LayoutClass::try_from(layout_class);
-        assert_eq!(max_width_variant, Err(()));
+        assert_eq!(max_width_variant, Err(ClassError::MissingValue));
+    }
+
+    #[test]
+    fn create_layout_class_with_typo_is_unknown() {
+        assert_eq!(LayoutClass::try_from("max-widht:60ch"), Err(ClassError::Unknown));
+        assert_eq!(LayoutClass::try_from("gap2"), Err(ClassError::Unknown));
+        assert_eq!(LayoutClass::try_from("gap:2x"), Err(ClassError::InvalidValue));
+        assert_eq!(LayoutClass::try_from("p:inf"), Err(ClassError::InvalidValue));
+        assert_eq!(LayoutClass::try_from("p:NaN"), Err(ClassError::InvalidValue));
+        assert_eq!(LayoutClass::try_from("p-child:--space"), Ok(LayoutClass::PChild("--space".into())));
+        assert_eq!(LayoutClass::try_from("foo:"), Err(ClassError::Unknown));
     }
 
     #[test]
     fn create_layout_class_from_text() {
         let layout_class = "max-width:440px";
         let max_width_variant = LayoutClass::try_from(layout_class);
-        assert_eq!(max_width_variant, Ok(LayoutClass::MaxWidth("440px")));
+        assert_eq!(max_width_variant, Ok(LayoutClass::MaxWidth("440px".into())));
     }
 }
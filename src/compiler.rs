@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::config::LayoutStyleConfig;
+use crate::diagnostic::Diagnostic;
+use crate::media_query::MediaQuery;
+use crate::{generate_final_css_with_config, insert_css_rules};
+
+/// The rules generated by a single document.
+#[derive(Debug, Default)]
+struct DocumentRules {
+    css_rules: HashSet<String>,
+    css_mq_rules: HashMap<MediaQuery, HashSet<String>>,
+}
+
+/// Incremental compiler keeping the css rules of many documents.
+/// Each rule is counted once per document using it, so when a document
+/// is updated or removed, the rules it was the last one to use disappear
+/// from the stylesheet without having to process the other documents again.
+#[derive(Debug)]
+pub struct LayoutCompiler {
+    config: LayoutStyleConfig,
+    documents: HashMap<String, DocumentRules>,
+    css_rules: HashMap<String, usize>,
+    css_mq_rules: HashMap<MediaQuery, HashMap<String, usize>>,
+}
+
+impl LayoutCompiler {
+    pub fn new(config: LayoutStyleConfig) -> Self {
+        LayoutCompiler {
+            config,
+            documents: HashMap::new(),
+            css_rules: HashMap::new(),
+            css_mq_rules: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &LayoutStyleConfig {
+        &self.config
+    }
+
+    /// return true if a document with this id has been added
+    pub fn contains_document(&self, id: &str) -> bool {
+        self.documents.contains_key(id)
+    }
+
+    /// number of documents added
+    pub fn documents_count(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Parse the text of the document and replace the rules previously
+    /// generated for it, return the problems found in the text.
+    pub fn update_document(&mut self, id: &str, text: &str) -> Vec<Diagnostic> {
+        let mut document = DocumentRules::default();
+        let diagnostics = insert_css_rules(
+            text,
+            &mut document.css_rules,
+            &mut document.css_mq_rules,
+            &self.config,
+        );
+        // rules are added before the old ones are released,
+        // so the rules still used are never removed from the maps
+        for rule in &document.css_rules {
+            *self.css_rules.entry(rule.clone()).or_insert(0) += 1;
+        }
+        for (mq, rules) in &document.css_mq_rules {
+            let counts = self.css_mq_rules.entry(mq.clone()).or_default();
+            for rule in rules {
+                *counts.entry(rule.clone()).or_insert(0) += 1;
+            }
+        }
+        if let Some(previous) = self.documents.insert(id.to_string(), document) {
+            self.release(previous);
+        }
+        diagnostics
+    }
+
+    /// Remove the document and the rules only used by it,
+    /// return false if there was no document with this id.
+    pub fn remove_document(&mut self, id: &str) -> bool {
+        match self.documents.remove(id) {
+            Some(document) => {
+                self.release(document);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn release(&mut self, document: DocumentRules) {
+        for rule in document.css_rules {
+            release_rule(&mut self.css_rules, rule);
+        }
+        for (mq, rules) in document.css_mq_rules {
+            if let Some(counts) = self.css_mq_rules.get_mut(&mq) {
+                for rule in rules {
+                    release_rule(counts, rule);
+                }
+                if counts.is_empty() {
+                    self.css_mq_rules.remove(&mq);
+                }
+            }
+        }
+    }
+
+    /// return the rules currently used by at least one document
+    pub fn rules(&self) -> (HashSet<String>, HashMap<MediaQuery, HashSet<String>>) {
+        let css_rules = self.css_rules.keys().cloned().collect();
+        let css_mq_rules = self
+            .css_mq_rules
+            .iter()
+            .map(|(mq, counts)| (mq.clone(), counts.keys().cloned().collect()))
+            .collect();
+        (css_rules, css_mq_rules)
+    }
+
+    /// generate the stylesheet of all the documents
+    pub fn css(&self) -> String {
+        let (css_rules, css_mq_rules) = self.rules();
+        generate_final_css_with_config(&css_rules, &css_mq_rules, &self.config)
+    }
+}
+
+/// decrement the count of the rule and remove it when nobody uses it anymore
+fn release_rule(counts: &mut HashMap<String, usize>, rule: String) {
+    if let Some(count) = counts.get_mut(&rule) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(&rule);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_stays_while_a_document_uses_it() {
+        let mut compiler = LayoutCompiler::new(LayoutStyleConfig::default());
+        compiler.update_document("a", "<stack-l layout=\"gap:2\">");
+        compiler.update_document("b", "<stack-l layout=\"gap:2\">");
+        compiler.remove_document("a");
+        assert!(compiler.css().contains("stack-l[layout~=\"gap:2\"]"));
+        compiler.remove_document("b");
+        assert!(!compiler.css().contains("stack-l"));
+    }
+
+    #[test]
+    fn updating_a_document_retracts_its_old_rules() {
+        let mut compiler = LayoutCompiler::new(LayoutStyleConfig::default());
+        compiler.update_document("a", "<row-l layout=\"gap:2\" layout600px=\"gap:1\">");
+        compiler.update_document("a", "<row-l layout=\"gap:3\">");
+        let css = compiler.css();
+        assert!(css.contains("row-l[layout~=\"gap:3\"]"));
+        assert!(!css.contains("gap:2"));
+        assert!(!css.contains("@media"));
+        assert_eq!(compiler.documents_count(), 1);
+    }
+
+    #[test]
+    fn removing_an_unknown_document_does_nothing() {
+        let mut compiler = LayoutCompiler::new(LayoutStyleConfig::default());
+        assert!(!compiler.remove_document("a"));
+        assert_eq!(compiler.css(), "");
+    }
+}
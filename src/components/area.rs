@@ -1,77 +1,112 @@
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
 
-pub const AREA_STYLE: &str = r#"
-area-l{
+pub fn area_style(names: &SelectorNames) -> String {
+    let area_l = names.tag("area");
+    format!(
+        r#"
+{area_l}{{
         display: grid;
-    }
-"#;
+    }}
+"#
+    )
+}
 
-fn area_gap_style(value: &str, harmonic: String) -> String {
+fn area_gap_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let area_l = names.tag("area");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        area-l[layout~="gap:{value}"]{{
+        {area_l}[{layout}~="gap:{value}"]{{
             gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn area_gap_x_style(value: &str, harmonic: String) -> String {
+fn area_gap_x_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let area_l = names.tag("area");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        area-l[layout~="gap-x:{value}"]{{
+        {area_l}[{layout}~="gap-x:{value}"]{{
             column-gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn area_gap_y_style(value: &str, harmonic: String) -> String {
+fn area_gap_y_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let area_l = names.tag("area");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        area-l[layout~="gap-y:{value}"]{{
+        {area_l}[{layout}~="gap-y:{value}"]{{
             row-gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn area_grid_template_areas_style(value: &str, template: String) -> String {
+fn area_grid_template_areas_style(value: &str, template: String, names: &SelectorNames) -> String {
+    let area_l = names.tag("area");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        area-l[layout~="template:{value}"] {{
+        {area_l}[{layout}~="template:{value}"] {{
                 grid-template-areas: {template};
             }}
         "#,
     )
 }
 
-fn area_grid_area_unit_style(value: &str, unit: char, index: usize) -> String {
+fn area_grid_area_unit_style(
+    value: &str,
+    unit: char,
+    index: usize,
+    names: &SelectorNames,
+) -> String {
+    let area_l = names.tag("area");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        area-l[layout~="template:{value}"] > :nth-child({index}) {{
+        {area_l}[{layout}~="template:{value}"] > :nth-child({index}) {{
             grid-area: {unit};
         }}
         "#,
     )
 }
 
-fn area_rows_style(selector: &str, value: &str, template_selector:&str) -> String {
+fn area_rows_style(
+    selector: &str,
+    value: &str,
+    template_selector: &str,
+    names: &SelectorNames,
+) -> String {
+    let area_l = names.tag("area");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        area-l[layout~="template:{template_selector}"]{selector}{{
+        {area_l}[{layout}~="template:{template_selector}"]{selector}{{
             grid-template-rows: {value};
         }}
         "#,
     )
 }
 
-fn area_cols_style(selector: &str, value: &str, template_selector:&str  ) -> String {
+fn area_cols_style(
+    selector: &str,
+    value: &str,
+    template_selector: &str,
+    names: &SelectorNames,
+) -> String {
+    let area_l = names.tag("area");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        area-l[layout~="template:{template_selector}"]{selector}{{
+        {area_l}[{layout}~="template:{template_selector}"]{selector}{{
             grid-template-columns: {value};
         }}
         "#,
@@ -130,12 +165,13 @@ fn grid_template_rows_or_cols_rule(items: &Vec<&str>, pattern: &str, number: usi
     rules.join(" ")
 }
 
-fn grid_template_rows_or_cols_selector(items: &Vec<&str>) -> String {
+fn grid_template_rows_or_cols_selector(items: &Vec<&str>, names: &SelectorNames) -> String {
+    let layout = &names.attribute;
     let formatted_items: Vec<String> = items
         .iter()
         .map(|item| {
             formatdoc!(
-                r#"[layout~="{}"]"#,
+                r#"[{layout}~="{}"]"#,
                 item
             )
         })
@@ -163,40 +199,41 @@ pub fn area_css(
     gap_x: Option<&str>,
     gap_y: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(AREA_STYLE.to_string());
+    set.insert(area_style(names));
     if let Some(template) = template {
         let template_areas = grid_template_areas_value(template);
-        set.insert(area_grid_template_areas_style(template, template_areas));
+        set.insert(area_grid_template_areas_style(template, template_areas, names));
         for (index, letter) in unique_letters(template).into_iter().enumerate() {
-            set.insert(area_grid_area_unit_style(template, letter, index + 1));
+            set.insert(area_grid_area_unit_style(template, letter, index + 1, names));
         }
 
         let (rows_nb, cols_nb) = count_rows_and_cols(template);
         if !rows.is_empty() {
-            let selector = grid_template_rows_or_cols_selector(&rows);
+            let selector = grid_template_rows_or_cols_selector(&rows, names);
             let value = grid_template_rows_or_cols_rule(&rows, "row-", rows_nb);
-            set.insert(area_rows_style(&selector, &value, template));
+            set.insert(area_rows_style(&selector, &value, template, names));
         }
         if !cols.is_empty() {
-            let selector = grid_template_rows_or_cols_selector(&cols );
+            let selector = grid_template_rows_or_cols_selector(&cols, names);
             let value = grid_template_rows_or_cols_rule(&cols, "col-", cols_nb);
-            set.insert(area_cols_style(&selector, &value,template));
+            set.insert(area_cols_style(&selector, &value, template, names));
         }
     }
 
     if let Some(value) = gap {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(area_gap_style(value, harmonic_value));
+        set.insert(area_gap_style(value, harmonic_value, names));
     }
     if let Some(value) = gap_x {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(area_gap_x_style(value, harmonic_value));
+        set.insert(area_gap_x_style(value, harmonic_value, names));
     }
     if let Some(value) = gap_y {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(area_gap_y_style(value, harmonic_value));
+        set.insert(area_gap_y_style(value, harmonic_value, names));
     }
 }
 
@@ -215,6 +252,7 @@ mod tests {
             None,
             None,
             1.618,
+            &SelectorNames::default(),
             &mut css_set,
         );
         println!("{:?}", css_set);
@@ -1,36 +1,55 @@
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
 
-const BOX_STYLE: &str = r#"
-  box-l{
+fn box_style(names: &SelectorNames) -> String {
+    let box_l = names.tag("box");
+    format!(
+        r#"
+  {box_l}{{
     box-sizing: border-box;
     display: block;
     max-inline-size:fit-content;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const BOX_GROW_STYLE: &str = r#"
-  box-l[layout~="grow"] > *{
+fn box_grow_style(names: &SelectorNames) -> String {
+    let box_l = names.tag("box");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {box_l}[{layout}~="grow"] > *{{
     width: 100%;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn box_max_width_style(value: &str) -> String {
+fn box_max_width_style(value: &str, names: &SelectorNames) -> String {
+    let box_l = names.tag("box");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        box-l[layout~="max-width:{value}"]{{
+        {box_l}[{layout}~="max-width:{value}"]{{
             max-inline-size:{value};
         }}
         "#,
     )
 }
 
-pub fn box_css(max_width: Option<&str>, grow: bool, set: &mut HashSet<String>) {
-    set.insert(BOX_STYLE.to_string());
+pub fn box_css(
+    max_width: Option<&str>,
+    grow: bool,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    set.insert(box_style(names));
     if let Some(value) = max_width {
-        set.insert(box_max_width_style(value));
+        set.insert(box_max_width_style(value, names));
     }
     if grow {
-        set.insert(BOX_GROW_STYLE.to_string());
+        set.insert(box_grow_style(names));
     }
 }
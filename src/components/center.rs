@@ -1,34 +1,54 @@
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
 
-const CENTER_STYLE: &str = r#"
-center-l{
+fn center_style(names: &SelectorNames) -> String {
+    let center_l = names.tag("center");
+    format!(
+        r#"
+{center_l}{{
     box-sizing: content-box;
     max-inline-size: fit-content;
     margin-inline: auto;
     display: block;
     text-align: initial;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const CENTER_AND_TEXT_STYLE: &str = r#"
-  center-l[layout~="and-text"]{
+fn center_and_text_style(names: &SelectorNames) -> String {
+    let center_l = names.tag("center");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {center_l}[{layout}~="and-text"]{{
     text-align:center;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const CENTER_RECURSIVE_STYLE: &str = r#"
-  center-l[layout~="recursive"]{
+fn center_recursive_style(names: &SelectorNames) -> String {
+    let center_l = names.tag("center");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {center_l}[{layout}~="recursive"]{{
     display:flex;
     flex-direction:column;
     align-items:center;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn center_max_width_style(value: &str) -> String {
+fn center_max_width_style(value: &str, names: &SelectorNames) -> String {
+    let center_l = names.tag("center");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        center-l[layout~="max-width:{value}"]{{
+        {center_l}[{layout}~="max-width:{value}"]{{
             max-inline-size: {value};
             --center-max-width: {value};
         }}
@@ -40,16 +60,17 @@ pub fn center_css(
     max_width: Option<&str>,
     and_text: bool,
     recursive: bool,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(CENTER_STYLE.to_string());
+    set.insert(center_style(names));
     if let Some(value) = max_width {
-        set.insert(center_max_width_style(value));
+        set.insert(center_max_width_style(value, names));
     }
     if and_text {
-        set.insert(CENTER_AND_TEXT_STYLE.to_string());
+        set.insert(center_and_text_style(names));
     }
     if recursive {
-        set.insert(CENTER_RECURSIVE_STYLE.to_string());
+        set.insert(center_recursive_style(names));
     }
 }
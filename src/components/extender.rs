@@ -1,52 +1,88 @@
+use crate::naming::SelectorNames;
 use std::collections::HashSet;
-const EXTENDER_STYLE: &str = r#"
-  extender-l{
+fn extender_style(names: &SelectorNames) -> String {
+    let extender_l = names.tag("extender");
+    format!(
+        r#"
+  {extender_l}{{
     display:block;
     width: calc(100% + var(--pr) + var(--pl));
     margin-inline-start: calc(0px - var(--pl));
     margin-inline-end: calc(0px - var(--pr));
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const EXTENDER_SCREEN_STYLE: &str = r#"
-  extender-l[layout~=screen]{
+fn extender_screen_style(names: &SelectorNames) -> String {
+    let extender_l = names.tag("extender");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {extender_l}[{layout}~=screen]{{
     width: 100cqw;
     position: relative;
     margin-left: -50cqw;
     margin-right: -50cqw;
     left: 50%;
     right: 50%;
-  }
-  "#;
+  }}
+  "#
+    )
+}
 
-const EXTENDER_KEEP_CENTER_STYLE: &str = r#"
-  extender-l[layout~="keep-center"] > *{
+fn extender_keep_center_style(names: &SelectorNames) -> String {
+    let extender_l = names.tag("extender");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {extender_l}[{layout}~="keep-center"] > *{{
     box-sizing: content-box;
     max-inline-size: var(--center-max-width);
     margin-inline: auto;
-  }
-  "#;
+  }}
+  "#
+    )
+}
 
-const EXTENDER_KEEP_P_STYLE: &str = r#"
-  extender-l[layout~="keep-p"] {
+fn extender_keep_p_style(names: &SelectorNames) -> String {
+    let extender_l = names.tag("extender");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {extender_l}[{layout}~="keep-p"] {{
     padding-right: var(--pr);
     padding-left: var(--pl);
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const EXTENDER_KEEP_PL_STYLE: &str = r#"
-  extender-l[layout~="keep-pl"] {
+fn extender_keep_pl_style(names: &SelectorNames) -> String {
+    let extender_l = names.tag("extender");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {extender_l}[{layout}~="keep-pl"] {{
     padding-left: var(--pl);
     padding-right: unset;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const EXTENDER_KEEP_PR_STYLE: &str = r#"
-  extender-l[layout~="keep-pr"] {
+fn extender_keep_pr_style(names: &SelectorNames) -> String {
+    let extender_l = names.tag("extender");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {extender_l}[{layout}~="keep-pr"] {{
     padding-right: var(--pr);
     padding-left: unset;
-  }
-"#;
+  }}
+"#
+    )
+}
 
 pub fn extender_css(
     screen: bool,
@@ -54,22 +90,23 @@ pub fn extender_css(
     keep_p: bool,
     keep_pl: bool,
     keep_pr: bool,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(EXTENDER_STYLE.to_string());
+    set.insert(extender_style(names));
     if screen {
-        set.insert(EXTENDER_SCREEN_STYLE.to_string());
+        set.insert(extender_screen_style(names));
     }
     if keep_center {
-        set.insert(EXTENDER_KEEP_CENTER_STYLE.to_string());
+        set.insert(extender_keep_center_style(names));
     }
     if keep_p {
-        set.insert(EXTENDER_KEEP_P_STYLE.to_string());
+        set.insert(extender_keep_p_style(names));
     }
     if keep_pl {
-        set.insert(EXTENDER_KEEP_PL_STYLE.to_string());
+        set.insert(extender_keep_pl_style(names));
     }
     if keep_pr {
-        set.insert(EXTENDER_KEEP_PR_STYLE.to_string());
+        set.insert(extender_keep_pr_style(names));
     }
 }
@@ -2,70 +2,98 @@ use indoc::formatdoc;
 use std::collections::HashSet;
 
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
-const GRID_STYLE: &str = r#"
-  grid-l{
+fn grid_style(names: &SelectorNames) -> String {
+    let grid_l = names.tag("grid");
+    format!(
+        r#"
+  {grid_l}{{
     display: grid;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn grid_gap_style(value: &str, harmonic: String) -> String {
+fn grid_gap_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let grid_l = names.tag("grid");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        grid-l[layout~="gap:{value}"]{{
+        {grid_l}[{layout}~="gap:{value}"]{{
             gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn grid_gap_x_style(value: &str, harmonic: String) -> String {
+fn grid_gap_x_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let grid_l = names.tag("grid");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        grid-l[layout~="gap-x:{value}"]{{
+        {grid_l}[{layout}~="gap-x:{value}"]{{
             column-gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn grid_gap_y_style(value: &str, harmonic: String) -> String {
+fn grid_gap_y_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let grid_l = names.tag("grid");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        grid-l[layout~="gap-y:{value}"]{{
+        {grid_l}[{layout}~="gap-y:{value}"]{{
             row-gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn grid_group_empty(min_cell_width: &str) -> String {
+fn grid_group_empty(min_cell_width: &str, names: &SelectorNames) -> String {
+    let grid_l = names.tag("grid");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        grid-l[layout*="min-cell-width:{min_cell_width}"] {{
+        {grid_l}[{layout}*="min-cell-width:{min_cell_width}"] {{
             grid-template-columns: repeat(auto-fit, minmax(min({min_cell_width}, 100%),1fr));
         }}
         "#,
     )
 }
 
-fn grid_group_max_cols(min_cell_width: &str, max_cols: &str, gap_delta_max: &str) -> String {
+fn grid_group_max_cols(
+    min_cell_width: &str,
+    max_cols: &str,
+    gap_delta_max: &str,
+    names: &SelectorNames,
+) -> String {
+    let grid_l = names.tag("grid");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        grid-l[layout*="min-cell-width:{min_cell_width}"][layout*="max-cols:{max_cols}"]{{
+        {grid_l}[{layout}*="min-cell-width:{min_cell_width}"][{layout}*="max-cols:{max_cols}"]{{
             grid-template-columns: repeat(auto-fit, minmax(min(100%, max({min_cell_width}, (100% / {max_cols} - {gap_delta_max}))),1fr));
         }}
         "#,
     )
 }
 
-fn grid_group_min_cols(min_cell_width: &str, min_cols: &str, gap_delta_min: &str) -> String {
+fn grid_group_min_cols(
+    min_cell_width: &str,
+    min_cols: &str,
+    gap_delta_min: &str,
+    names: &SelectorNames,
+) -> String {
+    let grid_l = names.tag("grid");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        grid-l[layout*="min-cell-width:{min_cell_width}"][layout*="min-cols:{min_cols}"]:has(:nth-child({min_cols})){{
+        {grid_l}[{layout}*="min-cell-width:{min_cell_width}"][{layout}*="min-cols:{min_cols}"]:has(:nth-child({min_cols})){{
             grid-template-columns: repeat(auto-fit, minmax(min((100% / {min_cols} - {gap_delta_min}), {min_cell_width}), 1fr));
         }}
-        grid-l[layout*="min-cell-width:{min_cell_width}"][layout*="min-cols:{min_cols}"]{{
+        {grid_l}[{layout}*="min-cell-width:{min_cell_width}"][{layout}*="min-cols:{min_cols}"]{{
             grid-template-columns: repeat({min_cols}, 1fr);
         }}
         "#,
@@ -79,10 +107,13 @@ fn grid_group_min_cols_max_cols(
     gap_delta_min: &str,
     gap_delta_max: &str,
     fr: f64,
+    names: &SelectorNames,
 ) -> String {
+    let grid_l = names.tag("grid");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        grid-l[layout*="min-cell-width:{min_cell_width}"][layout*="min-cols:{min_cols}"][layout*="max-cols:{max_cols}"]:has(:nth-child({min_cols})){{
+        {grid_l}[{layout}*="min-cell-width:{min_cell_width}"][{layout}*="min-cols:{min_cols}"][{layout}*="max-cols:{max_cols}"]:has(:nth-child({min_cols})){{
             grid-template-columns:
                 repeat(auto-fit,
                     minmax(
@@ -94,7 +125,7 @@ fn grid_group_min_cols_max_cols(
                             )
                         )
                     }}
-        grid-l[layout*="min-cell-width:{min_cell_width}"][layout*="min-cols:{min_cols}"][layout*="max-cols:{max_cols}"]{{
+        {grid_l}[{layout}*="min-cell-width:{min_cell_width}"][{layout}*="min-cols:{min_cols}"][{layout}*="max-cols:{max_cols}"]{{
             grid-template-columns: repeat({min_cols}, 1fr);
         }}
         "#,
@@ -124,20 +155,21 @@ pub fn grid_css(
     gap_x: Option<&str>,
     gap_y: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(GRID_STYLE.to_string());
+    set.insert(grid_style(names));
     if let Some(ref value) = gap {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(grid_gap_style(value, harmonic_value));
+        set.insert(grid_gap_style(value, harmonic_value, names));
     }
     if let Some(ref value) = gap_x {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(grid_gap_x_style(value, harmonic_value));
+        set.insert(grid_gap_x_style(value, harmonic_value, names));
     }
     if let Some(ref value) = gap_y {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(grid_gap_y_style(value, harmonic_value));
+        set.insert(grid_gap_y_style(value, harmonic_value, names));
     }
     if let Some(min_cell_width) = min_cell_width {
         match (min_cols, max_cols) {
@@ -153,18 +185,19 @@ pub fn grid_css(
                     &gap_delta_min,
                     &gap_delta_max,
                     fr,
+                    names,
                 ));
             }
             (Some(min_cols), None) => {
                 let gap_delta_min = gap_delta(min_cols, gap, harmonic_ratio);
-                set.insert(grid_group_min_cols(min_cell_width, min_cols, &gap_delta_min));
+                set.insert(grid_group_min_cols(min_cell_width, min_cols, &gap_delta_min, names));
             }
             (None, Some(max_cols)) => {
                 let gap_delta_max = gap_delta(max_cols, gap, harmonic_ratio);
-                set.insert(grid_group_max_cols(min_cell_width, max_cols, &gap_delta_max));
+                set.insert(grid_group_max_cols(min_cell_width, max_cols, &gap_delta_max, names));
             }
             _ => {
-                set.insert(grid_group_empty(min_cell_width));
+                set.insert(grid_group_empty(min_cell_width, names));
             }
         }
     }
@@ -2,34 +2,44 @@ use indoc::formatdoc;
 use std::collections::HashSet;
 
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
-const ICON_STYLE: &str = r#"
-icon-l{
+fn icon_style(names: &SelectorNames) -> String {
+    let icon_l = names.tag("icon");
+    format!(
+        r#"
+{icon_l}{{
     display: inline-block;
     width: fit-content;
     vertical-align: middle;
-}
+}}
 
-icon-l > :nth-child(1) {
+{icon_l} > :nth-child(1) {{
     height: 0.75em;
     width: auto;
+}}
+"#
+    )
 }
-"#;
 
-fn icon_scale_style(value: &str, harmonic: String) -> String {
+fn icon_scale_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let icon_l = names.tag("icon");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        icon-l[layout*="scale:{value}"] >:nth-child(1){{
+        {icon_l}[{layout}*="scale:{value}"] >:nth-child(1){{
             height: {harmonic};
         }}
         "#,
     )
 }
 
-fn icon_align_style(value: &str) -> String {
+fn icon_align_style(value: &str, names: &SelectorNames) -> String {
+    let icon_l = names.tag("icon");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        icon-l[layout~="align:{value}"]{{
+        {icon_l}[{layout}~="align:{value}"]{{
             vertical-align: {value};
         }}
         "#,
@@ -42,10 +52,13 @@ fn icon_group_style(
     gap_dir: &str,
     opposite_dir: &str,
     harmonic: String,
+    names: &SelectorNames,
 ) -> String {
+    let icon_l = names.tag("icon");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        icon-l{gap_dir_selector}[layout*="gap:{value}"] >:nth-child(1){{
+        {icon_l}{gap_dir_selector}[{layout}*="gap:{value}"] >:nth-child(1){{
             margin-inline-{gap_dir}: {harmonic};
             margin-inline-{opposite_dir}: initial;
         }}
@@ -59,15 +72,17 @@ pub fn icon_css(
     gap_dir: Option<&str>,
     gap: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(ICON_STYLE.to_string());
+    let layout = &names.attribute;
+    set.insert(icon_style(names));
     if let Some(value) = scale {
         let harmonic_value = get_harmonic(&value, harmonic_ratio);
-        set.insert(icon_scale_style(value, harmonic_value));
+        set.insert(icon_scale_style(value, harmonic_value, names));
     }
     if let Some(value) = align {
-        set.insert(icon_align_style(value));
+        set.insert(icon_align_style(value, names));
     }
     if let Some(value) = gap {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
@@ -76,17 +91,18 @@ pub fn icon_css(
             _ => "start",
         };
         let gap_dir_selector = if gap_dir == "end" {
-            r#"[layout*="gap-dir:end"]"#
+            format!(r#"[{layout}*="gap-dir:end"]"#)
         } else {
-            ""
+            String::new()
         };
         let opposite_dir = if gap_dir == "end" { "start" } else { "end" };
         set.insert(icon_group_style(
             value,
-            gap_dir_selector,
+            &gap_dir_selector,
             gap_dir,
             opposite_dir,
             harmonic_value,
+            names,
         ));
     }
 }
@@ -1,4 +1,7 @@
-use std::{collections::HashSet, hash::Hash, str::FromStr};
+use std::{borrow::Cow, collections::HashSet, hash::Hash, str::FromStr};
+use serde::{Deserialize, Serialize};
+
+use crate::naming::SelectorNames;
 
 use area::area_css;
 use center::center_css;
@@ -28,22 +31,39 @@ pub mod slider;
 pub mod stack;
 pub mod switcher;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+/// Tag names of the components, in the order their css is written in the stylesheet.
+pub const COMPONENT_TAGS: [&str; 13] = [
+    "area-l",
+    "box-l",
+    "center-l",
+    "extender-l",
+    "grid-l",
+    "icon-l",
+    "row-l",
+    "outsider-l",
+    "rack-l",
+    "sidebar-l",
+    "slider-l",
+    "stack-l",
+    "switcher-l",
+];
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum Component<'a> {
     Area {
-        template: Option<&'a str>,
-        rows: Vec<&'a str>,
-        cols: Vec<&'a str>,
-        gap: Option<&'a str>,
-        gap_x: Option<&'a str>,
-        gap_y: Option<&'a str>,
+        template: Option<Cow<'a, str>>,
+        rows: Vec<Cow<'a, str>>,
+        cols: Vec<Cow<'a, str>>,
+        gap: Option<Cow<'a, str>>,
+        gap_x: Option<Cow<'a, str>>,
+        gap_y: Option<Cow<'a, str>>,
     },
     Box {
-        max_width: Option<&'a str>,
+        max_width: Option<Cow<'a, str>>,
         grow: bool,
     },
     Center {
-        max_width: Option<&'a str>,
+        max_width: Option<Cow<'a, str>>,
         and_text: bool,
         recursive: bool,
     },
@@ -55,69 +75,69 @@ pub enum Component<'a> {
         keep_pr: bool,
     },
     Grid {
-        min_cell_width: Option<&'a str>,
-        min_cols: Option<&'a str>,
-        max_cols: Option<&'a str>,
-        gap: Option<&'a str>,
-        gap_x: Option<&'a str>,
-        gap_y: Option<&'a str>,
+        min_cell_width: Option<Cow<'a, str>>,
+        min_cols: Option<Cow<'a, str>>,
+        max_cols: Option<Cow<'a, str>>,
+        gap: Option<Cow<'a, str>>,
+        gap_x: Option<Cow<'a, str>>,
+        gap_y: Option<Cow<'a, str>>,
     },
     Icon {
-        scale: Option<&'a str>,
-        align: Option<&'a str>,
-        gap_dir: Option<&'a str>,
-        gap: Option<&'a str>,
+        scale: Option<Cow<'a, str>>,
+        align: Option<Cow<'a, str>>,
+        gap_dir: Option<Cow<'a, str>>,
+        gap: Option<Cow<'a, str>>,
     },
     Row {
         nowrap: bool,
         twin_width: bool,
-        direction: Option<&'a str>,
-        justify: Option<&'a str>,
-        align: Option<&'a str>,
-        gap: Option<&'a str>,
-        gap_x: Option<&'a str>,
-        gap_y: Option<&'a str>,
+        direction: Option<Cow<'a, str>>,
+        justify: Option<Cow<'a, str>>,
+        align: Option<Cow<'a, str>>,
+        gap: Option<Cow<'a, str>>,
+        gap_x: Option<Cow<'a, str>>,
+        gap_y: Option<Cow<'a, str>>,
     },
     Outsider {
-        position: Option<&'a str>,
-        top: Option<&'a str>,
-        bottom: Option<&'a str>,
-        left: Option<&'a str>,
-        right: Option<&'a str>,
+        position: Option<Cow<'a, str>>,
+        top: Option<Cow<'a, str>>,
+        bottom: Option<Cow<'a, str>>,
+        left: Option<Cow<'a, str>>,
+        right: Option<Cow<'a, str>>,
     },
     Rack {
-        height: Option<&'a str>,
-        min_height: Option<&'a str>,
-        max_height: Option<&'a str>,
-        gap: Option<&'a str>,
+        height: Option<Cow<'a, str>>,
+        min_height: Option<Cow<'a, str>>,
+        max_height: Option<Cow<'a, str>>,
+        gap: Option<Cow<'a, str>>,
     },
     Sidebar {
         reverse: bool,
         shrink: bool,
-        side: Option<&'a str>,
-        side_width: Option<&'a str>,
-        content_min: Option<&'a str>,
-        gap: Option<&'a str>,
-        gap_x: Option<&'a str>,
-        gap_y: Option<&'a str>,
+        side: Option<Cow<'a, str>>,
+        side_width: Option<Cow<'a, str>>,
+        content_min: Option<Cow<'a, str>>,
+        gap: Option<Cow<'a, str>>,
+        gap_x: Option<Cow<'a, str>>,
+        gap_y: Option<Cow<'a, str>>,
     },
     Slider {
         hide_bar: bool,
-        item_width: Option<&'a str>,
-        height: Option<&'a str>,
-        gap: Option<&'a str>,
+        item_width: Option<Cow<'a, str>>,
+        height: Option<Cow<'a, str>>,
+        gap: Option<Cow<'a, str>>,
     },
     Stack {
-        gap: Option<&'a str>,
+        gap: Option<Cow<'a, str>>,
         recursive: bool,
     },
     Switcher {
-        threshold: Option<&'a str>,
-        limit: Option<&'a str>,
+        threshold: Option<Cow<'a, str>>,
+        limit: Option<Cow<'a, str>>,
         reverse: bool,
-        gap: Option<&'a str>,
-        gap_x: Option<&'a str>,
-        gap_y: Option<&'a str>,
+        gap: Option<Cow<'a, str>>,
+        gap_x: Option<Cow<'a, str>>,
+        gap_y: Option<Cow<'a, str>>,
     },
 }
 
@@ -221,11 +241,154 @@ impl<'a> FromStr for Component<'a> {
 }
 
 impl<'a> Component<'a> {
+    /// return the same component owning its values, so it can outlive the parsed text
+    pub fn into_owned(self) -> Component<'static> {
+        match self {
+            Component::Area { template, rows, cols, gap, gap_x, gap_y } => Component::Area {
+                template: owned_value(template),
+                rows: rows.into_iter().map(|value| Cow::Owned(value.into_owned())).collect(),
+                cols: cols.into_iter().map(|value| Cow::Owned(value.into_owned())).collect(),
+                gap: owned_value(gap),
+                gap_x: owned_value(gap_x),
+                gap_y: owned_value(gap_y),
+            },
+            Component::Box { max_width, grow } => Component::Box {
+                max_width: owned_value(max_width),
+                grow,
+            },
+            Component::Center { max_width, and_text, recursive } => Component::Center {
+                max_width: owned_value(max_width),
+                and_text,
+                recursive,
+            },
+            Component::Extender {
+                screen,
+                keep_center,
+                keep_p,
+                keep_pl,
+                keep_pr,
+            } => Component::Extender {
+                screen,
+                keep_center,
+                keep_p,
+                keep_pl,
+                keep_pr,
+            },
+            Component::Grid {
+                min_cell_width,
+                min_cols,
+                max_cols,
+                gap,
+                gap_x,
+                gap_y,
+            } => Component::Grid {
+                min_cell_width: owned_value(min_cell_width),
+                min_cols: owned_value(min_cols),
+                max_cols: owned_value(max_cols),
+                gap: owned_value(gap),
+                gap_x: owned_value(gap_x),
+                gap_y: owned_value(gap_y),
+            },
+            Component::Icon { scale, align, gap_dir, gap } => Component::Icon {
+                scale: owned_value(scale),
+                align: owned_value(align),
+                gap_dir: owned_value(gap_dir),
+                gap: owned_value(gap),
+            },
+            Component::Row {
+                nowrap,
+                twin_width,
+                direction,
+                justify,
+                align,
+                gap,
+                gap_x,
+                gap_y,
+            } => Component::Row {
+                nowrap,
+                twin_width,
+                direction: owned_value(direction),
+                justify: owned_value(justify),
+                align: owned_value(align),
+                gap: owned_value(gap),
+                gap_x: owned_value(gap_x),
+                gap_y: owned_value(gap_y),
+            },
+            Component::Outsider { position, top, bottom, left, right } => Component::Outsider {
+                position: owned_value(position),
+                top: owned_value(top),
+                bottom: owned_value(bottom),
+                left: owned_value(left),
+                right: owned_value(right),
+            },
+            Component::Rack { height, min_height, max_height, gap } => Component::Rack {
+                height: owned_value(height),
+                min_height: owned_value(min_height),
+                max_height: owned_value(max_height),
+                gap: owned_value(gap),
+            },
+            Component::Sidebar {
+                reverse,
+                shrink,
+                side,
+                side_width,
+                content_min,
+                gap,
+                gap_x,
+                gap_y,
+            } => Component::Sidebar {
+                reverse,
+                shrink,
+                side: owned_value(side),
+                side_width: owned_value(side_width),
+                content_min: owned_value(content_min),
+                gap: owned_value(gap),
+                gap_x: owned_value(gap_x),
+                gap_y: owned_value(gap_y),
+            },
+            Component::Slider { hide_bar, item_width, height, gap } => Component::Slider {
+                hide_bar,
+                item_width: owned_value(item_width),
+                height: owned_value(height),
+                gap: owned_value(gap),
+            },
+            Component::Stack { gap, recursive } => Component::Stack {
+                gap: owned_value(gap),
+                recursive,
+            },
+            Component::Switcher {
+                threshold,
+                limit,
+                reverse,
+                gap,
+                gap_x,
+                gap_y,
+            } => Component::Switcher {
+                threshold: owned_value(threshold),
+                limit: owned_value(limit),
+                reverse,
+                gap: owned_value(gap),
+                gap_x: owned_value(gap_x),
+                gap_y: owned_value(gap_y),
+            },
+        }
+    }
+
     /// Insert the css of the component
     /// inside the HashSet passed.
     /// This method consumes the component, because we dont need it anymore
     /// after we have inserted the css.
     pub fn insert_css(self, harmonic_ratio: f64, set: &mut HashSet<String>) {
+        self.insert_css_with_names(harmonic_ratio, &SelectorNames::default(), set)
+    }
+
+    /// Same as insert_css, but the css selects the elements with the given names.
+    pub fn insert_css_with_names(
+        self,
+        harmonic_ratio: f64,
+        names: &SelectorNames,
+        set: &mut HashSet<String>,
+    ) {
         match self {
             Component::Area {
                 template,
@@ -234,20 +397,30 @@ impl<'a> Component<'a> {
                 gap,
                 gap_x,
                 gap_y,
-            } => area_css(template, rows, cols, gap, gap_x, gap_y, harmonic_ratio, set),
-            Component::Box { max_width, grow } => box_css(max_width, grow, set),
+            } => area_css(
+                template.as_deref(),
+                rows.iter().map(AsRef::as_ref).collect(),
+                cols.iter().map(AsRef::as_ref).collect(),
+                gap.as_deref(),
+                gap_x.as_deref(),
+                gap_y.as_deref(),
+                harmonic_ratio,
+                names,
+                set,
+            ),
+            Component::Box { max_width, grow } => box_css(max_width.as_deref(), grow, names, set),
             Component::Center {
                 max_width,
                 and_text,
                 recursive,
-            } => center_css(max_width, and_text, recursive, set),
+            } => center_css(max_width.as_deref(), and_text, recursive, names, set),
             Component::Extender {
                 screen,
                 keep_center,
                 keep_p,
                 keep_pl,
                 keep_pr,
-            } => extender_css(screen, keep_center,keep_p, keep_pl, keep_pr,  set),
+            } => extender_css(screen, keep_center, keep_p, keep_pl, keep_pr, names, set),
             Component::Grid {
                 min_cell_width,
                 min_cols,
@@ -256,13 +429,14 @@ impl<'a> Component<'a> {
                 gap_x,
                 gap_y,
             } => grid_css(
-                min_cell_width,
-                min_cols,
-                max_cols,
-                gap,
-                gap_x,
-                gap_y,
+                min_cell_width.as_deref(),
+                min_cols.as_deref(),
+                max_cols.as_deref(),
+                gap.as_deref(),
+                gap_x.as_deref(),
+                gap_y.as_deref(),
                 harmonic_ratio,
+                names,
                 set,
             ),
             Component::Icon {
@@ -270,7 +444,15 @@ impl<'a> Component<'a> {
                 align,
                 gap_dir,
                 gap,
-            } => icon_css(scale, align, gap_dir, gap, harmonic_ratio, set),
+            } => icon_css(
+                scale.as_deref(),
+                align.as_deref(),
+                gap_dir.as_deref(),
+                gap.as_deref(),
+                harmonic_ratio,
+                names,
+                set,
+            ),
             Component::Row {
                 nowrap,
                 twin_width,
@@ -283,13 +465,14 @@ impl<'a> Component<'a> {
             } => row_css(
                 nowrap,
                 twin_width,
-                direction,
-                justify,
-                align,
-                gap,
-                gap_x,
-                gap_y,
+                direction.as_deref(),
+                justify.as_deref(),
+                align.as_deref(),
+                gap.as_deref(),
+                gap_x.as_deref(),
+                gap_y.as_deref(),
                 harmonic_ratio,
+                names,
                 set,
             ),
             Component::Outsider {
@@ -298,13 +481,30 @@ impl<'a> Component<'a> {
                 bottom,
                 left,
                 right,
-            } => outsider_css(position, top, bottom, left, right, harmonic_ratio, set),
+            } => outsider_css(
+                position.as_deref(),
+                top.as_deref(),
+                bottom.as_deref(),
+                left.as_deref(),
+                right.as_deref(),
+                harmonic_ratio,
+                names,
+                set,
+            ),
             Component::Rack {
                 height,
                 min_height,
                 max_height,
                 gap,
-            } => rack_css(height, min_height, max_height, gap, harmonic_ratio, set),
+            } => rack_css(
+                height.as_deref(),
+                min_height.as_deref(),
+                max_height.as_deref(),
+                gap.as_deref(),
+                harmonic_ratio,
+                names,
+                set,
+            ),
             Component::Sidebar {
                 reverse,
                 shrink,
@@ -317,13 +517,14 @@ impl<'a> Component<'a> {
             } => sidebar_css(
                 reverse,
                 shrink,
-                side,
-                side_width,
-                content_min,
-                gap,
-                gap_x,
-                gap_y,
+                side.as_deref(),
+                side_width.as_deref(),
+                content_min.as_deref(),
+                gap.as_deref(),
+                gap_x.as_deref(),
+                gap_y.as_deref(),
                 harmonic_ratio,
+                names,
                 set,
             ),
             Component::Slider {
@@ -331,8 +532,16 @@ impl<'a> Component<'a> {
                 item_width,
                 height,
                 gap,
-            } => slider_css(hide_bar, item_width, height, gap, harmonic_ratio, set),
-            Component::Stack { gap, recursive } => stack_css(gap, recursive, harmonic_ratio, set),
+            } => slider_css(
+                hide_bar,
+                item_width.as_deref(),
+                height.as_deref(),
+                gap.as_deref(),
+                harmonic_ratio,
+                names,
+                set,
+            ),
+            Component::Stack { gap, recursive } => stack_css(gap.as_deref(), recursive, harmonic_ratio, names, set),
             Component::Switcher {
                 threshold,
                 limit,
@@ -341,19 +550,25 @@ impl<'a> Component<'a> {
                 gap_x,
                 gap_y,
             } => switcher_css(
-                threshold,
-                limit,
+                threshold.as_deref(),
+                limit.as_deref(),
                 reverse,
-                gap,
-                gap_x,
-                gap_y,
+                gap.as_deref(),
+                gap_x.as_deref(),
+                gap_y.as_deref(),
                 harmonic_ratio,
+                names,
                 set,
             ),
         };
     }
 }
 
+/// return the value owning its text
+fn owned_value(value: Option<Cow<str>>) -> Option<Cow<'static, str>> {
+    value.map(|value| Cow::Owned(value.into_owned()))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -363,7 +578,7 @@ mod tests {
     fn test_get_css_on_box() {
         let mut set: HashSet<String> = HashSet::new();
         let box_component = Component::Box {
-            max_width: Some("440px"),
+            max_width: Some("440px".into()),
             grow: false,
         };
         box_component.insert_css(1.618, &mut set);
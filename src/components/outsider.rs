@@ -1,63 +1,80 @@
 use indoc::formatdoc;
 use std::collections::HashSet;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
-const OUTSIDER_STYLE: &str = r#"
-  outsider-l{
+fn outsider_style(names: &SelectorNames) -> String {
+    let outsider_l = names.tag("outsider");
+    format!(
+        r#"
+  {outsider_l}{{
     display:block;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn outsider_position_style(value: &str) -> String {
+fn outsider_position_style(value: &str, names: &SelectorNames) -> String {
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        outsider-l[layout~="position:{value}"]{{
+        {outsider_l}[{layout}~="position:{value}"]{{
             position:{value};
         }}
         "#,
     )
 }
 
-fn outsider_top_style(value: &str, harmonic: String) -> String {
+fn outsider_top_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        outsider-l[layout~="top:{value}"]{{
+        {outsider_l}[{layout}~="top:{value}"]{{
             top:{harmonic};
         }}
         "#,
     )
 }
 
-fn outsider_bottom_style(value: &str, harmonic: String) -> String {
+fn outsider_bottom_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        outsider-l[layout~="bottom:{value}"]{{
+        {outsider_l}[{layout}~="bottom:{value}"]{{
             bottom:{harmonic};
         }}
         "#,
     )
 }
 
-fn outsider_left_style(value: &str, harmonic: String) -> String {
+fn outsider_left_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        outsider-l[layout~="left:{value}"]{{
+        {outsider_l}[{layout}~="left:{value}"]{{
             left:{harmonic};
         }}
         "#,
     )
 }
 
-fn outsider_right_style(value: &str, harmonic: String) -> String {
+fn outsider_right_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        outsider-l[layout~="right:{value}"]{{
+        {outsider_l}[{layout}~="right:{value}"]{{
             right:{harmonic};
         }}
         "#,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn outsider_css(
     position: Option<&str>,
     top: Option<&str>,
@@ -65,28 +82,29 @@ pub fn outsider_css(
     left: Option<&str>,
     right: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(OUTSIDER_STYLE.to_string());
+    set.insert(outsider_style(names));
 
     if let Some(value) = position {
-        set.insert(outsider_position_style(value));
+        set.insert(outsider_position_style(value, names));
     }
 
     if let Some(value) = top {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(outsider_top_style(value, harmonic_value));
+        set.insert(outsider_top_style(value, harmonic_value, names));
     }
     if let Some(value) = bottom {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(outsider_bottom_style(value, harmonic_value));
+        set.insert(outsider_bottom_style(value, harmonic_value, names));
     }
     if let Some(value) = left {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(outsider_left_style(value, harmonic_value));
+        set.insert(outsider_left_style(value, harmonic_value, names));
     }
     if let Some(value) = right {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(outsider_right_style(value, harmonic_value));
+        set.insert(outsider_right_style(value, harmonic_value, names));
     }
 }
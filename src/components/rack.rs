@@ -1,30 +1,40 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
 use std::collections::HashSet;
-const RACK_STYLE: &str = r#"
-  rack-l{
+fn rack_style(names: &SelectorNames) -> String {
+    let rack_l = names.tag("rack");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {rack_l}{{
     display:flex;
     flex-direction:column;
-  }
+  }}
 
-  rack-l > [layout~="centered"]{
+  {rack_l} > [{layout}~="centered"]{{
       margin-block: auto;
-  }
+  }}
 
-  rack-l > :first-child:not([layout~="centered"]):not(outsider-l[layout~="disinherit"]) {
+  {rack_l} > :first-child:not([{layout}~="centered"]):not({outsider_l}[{layout}~="disinherit"]) {{
       margin-block-start: 0;
-  }
+  }}
 
-  rack-l > :last-child:not([layout~="centered"]):not(outsider-l[layout~="disinherit"]) {
+  {rack_l} > :last-child:not([{layout}~="centered"]):not({outsider_l}[{layout}~="disinherit"]) {{
       margin-block-end: 0;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn rack_height_style(value: &str, harmonic: String) -> String {
+fn rack_height_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let rack_l = names.tag("rack");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        rack-l[layout~="height:{value}"]{{
+        {rack_l}[{layout}~="height:{value}"]{{
             height: {harmonic};
             overflow-y: auto;
         }}
@@ -32,30 +42,36 @@ fn rack_height_style(value: &str, harmonic: String) -> String {
     )
 }
 
-fn rack_min_height_style(value: &str, harmonic: String) -> String {
+fn rack_min_height_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let rack_l = names.tag("rack");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        rack-l[layout~="min-height:{value}"]{{
+        {rack_l}[{layout}~="min-height:{value}"]{{
             min-height: {harmonic};
         }}
         "#,
     )
 }
 
-fn rack_max_height_style(value: &str, harmonic: String) -> String {
+fn rack_max_height_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let rack_l = names.tag("rack");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        rack-l[layout~="max-height:{value}"]{{
+        {rack_l}[{layout}~="max-height:{value}"]{{
             max-height: {harmonic};
         }}
         "#,
     )
 }
 
-fn rack_gap_style(value: &str, harmonic: String) -> String {
+fn rack_gap_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let rack_l = names.tag("rack");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        rack-l[layout~="gap:{value}"]{{
+        {rack_l}[{layout}~="gap:{value}"]{{
             gap: {harmonic};
         }}
         "#,
@@ -68,23 +84,24 @@ pub fn rack_css(
     max_height: Option<&str>,
     gap: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(RACK_STYLE.to_string());
+    set.insert(rack_style(names));
     if let Some(value) = height {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(rack_height_style(value, harmonic_value));
+        set.insert(rack_height_style(value, harmonic_value, names));
     }
     if let Some(value) = min_height {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(rack_min_height_style(value, harmonic_value));
+        set.insert(rack_min_height_style(value, harmonic_value, names));
     }
     if let Some(value) = max_height {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(rack_max_height_style(value, harmonic_value));
+        set.insert(rack_max_height_style(value, harmonic_value, names));
     }
     if let Some(value) = gap {
         let harmonic_value = get_harmonic(&value, harmonic_ratio);
-        set.insert(rack_gap_style(value, harmonic_value));
+        set.insert(rack_gap_style(value, harmonic_value, names));
     }
 }
@@ -1,83 +1,113 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 use std::collections::HashSet;
-const ROW_STYLE: &str = r#"
-  row-l{
+fn row_style(names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    format!(
+        r#"
+  {row_l}{{
     display:flex;
     flex-wrap: wrap;
-  }
-  row-l > * {
+  }}
+  {row_l} > * {{
     min-width: 0;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const ROW_NO_WRAP_STYLE: &str = r#"
-  row-l[layout~="nowrap"]  {
+fn row_no_wrap_style(names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {row_l}[{layout}~="nowrap"]  {{
       flex-wrap:nowrap;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const ROW_TWIN_WIDTH_STYLE: &str = r#"
-  row-l[layout~="twin-width"] > * {
+fn row_twin_width_style(names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {row_l}[{layout}~="twin-width"] > * {{
       flex-grow:1;
       flex-basis:0;
       min-width: 0;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn row_direction_style(value: &str) -> String {
+fn row_direction_style(value: &str, names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        row-l[layout~="direction:{value}"]{{
+        {row_l}[{layout}~="direction:{value}"]{{
             flex-direction: {value};
         }}
         "#,
     )
 }
 
-fn row_justify_style(value: &str) -> String {
+fn row_justify_style(value: &str, names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        row-l[layout~="justify:{value}"]{{
+        {row_l}[{layout}~="justify:{value}"]{{
             justify-content: {value};
         }}
         "#,
     )
 }
 
-fn row_align_style(value: &str) -> String {
+fn row_align_style(value: &str, names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        row-l[layout~="align:{value}"]{{
+        {row_l}[{layout}~="align:{value}"]{{
             align-items: {value};
         }}
         "#,
     )
 }
-fn row_gap_style(value: &str, harmonic: String) -> String {
+fn row_gap_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        row-l[layout~="gap:{value}"]{{
+        {row_l}[{layout}~="gap:{value}"]{{
             gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn row_gap_x_style(value: &str, harmonic: String) -> String {
+fn row_gap_x_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        row-l[layout~="gap-x:{value}"]{{
+        {row_l}[{layout}~="gap-x:{value}"]{{
             column-gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn row_gap_y_style(value: &str, harmonic: String) -> String {
+fn row_gap_y_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let row_l = names.tag("row");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        row-l[layout~="gap-y:{value}"]{{
+        {row_l}[{layout}~="gap-y:{value}"]{{
             row-gap: {harmonic};
         }}
         "#,
@@ -94,35 +124,36 @@ pub fn row_css(
     gap_x: Option<&str>,
     gap_y: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(ROW_STYLE.to_string());
+    set.insert(row_style(names));
 
     if nowrap {
-        set.insert(ROW_NO_WRAP_STYLE.to_string());
+        set.insert(row_no_wrap_style(names));
     }
     if twin_width {
-        set.insert(ROW_TWIN_WIDTH_STYLE.to_string());
+        set.insert(row_twin_width_style(names));
     }
     if let Some(value) = direction {
-        set.insert(row_direction_style(value));
+        set.insert(row_direction_style(value, names));
     }
     if let Some(value) = justify {
-        set.insert(row_justify_style(value));
+        set.insert(row_justify_style(value, names));
     }
     if let Some(value) = align {
-        set.insert(row_align_style(value));
+        set.insert(row_align_style(value, names));
     }
     if let Some(value) = gap {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(row_gap_style(value, harmonic_value));
+        set.insert(row_gap_style(value, harmonic_value, names));
     }
     if let Some(ref value) = gap_x {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(row_gap_x_style(value, harmonic_value));
+        set.insert(row_gap_x_style(value, harmonic_value, names));
     }
     if let Some(ref value) = gap_y {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(row_gap_y_style(value, harmonic_value));
+        set.insert(row_gap_y_style(value, harmonic_value, names));
     }
 }
@@ -1,24 +1,38 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
 use std::collections::HashSet;
-const SIDEBAR_STYLE: &str = r#"
-  sidebar-l{
+fn sidebar_style(names: &SelectorNames) -> String {
+    let sidebar_l = names.tag("sidebar");
+    format!(
+        r#"
+  {sidebar_l}{{
     display: flex;
     flex-wrap: wrap;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const SIDEBAR_REVERSE_STYLE: &str = r#"
-  sidebar-l[layout~=reverse]{
+fn sidebar_reverse_style(names: &SelectorNames) -> String {
+    let sidebar_l = names.tag("sidebar");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {sidebar_l}[{layout}~=reverse]{{
     flex-wrap: wrap-reverse;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn sidebar_shrink_style(reverse: bool) -> String {
+fn sidebar_shrink_style(reverse: bool, names: &SelectorNames) -> String {
+    let sidebar_l = names.tag("sidebar");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        sidebar-l[layout~=shrink]{{
+        {sidebar_l}[{layout}~=shrink]{{
             align-items: flex-{};
         }}
         "#,
@@ -26,30 +40,36 @@ fn sidebar_shrink_style(reverse: bool) -> String {
     )
 }
 
-fn sidebar_gap_style(value: &str, harmonic: String) -> String {
+fn sidebar_gap_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let sidebar_l = names.tag("sidebar");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        sidebar-l[layout~="gap:{value}"]{{
+        {sidebar_l}[{layout}~="gap:{value}"]{{
             gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn sidebar_gap_x_style(value: &str, harmonic: String) -> String {
+fn sidebar_gap_x_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let sidebar_l = names.tag("sidebar");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        sidebar-l[layout~="gap-x:{value}"]{{
+        {sidebar_l}[{layout}~="gap-x:{value}"]{{
             column-gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn sidebar_gap_y_style(value: &str, harmonic: String) -> String {
+fn sidebar_gap_y_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let sidebar_l = names.tag("sidebar");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        sidebar-l[layout~="gap-y:{value}"]{{
+        {sidebar_l}[{layout}~="gap-y:{value}"]{{
             row-gap: {harmonic};
         }}
         "#,
@@ -57,19 +77,21 @@ fn sidebar_gap_y_style(value: &str, harmonic: String) -> String {
 }
 
 fn sidebar_group_style(
-    // they are String instead of &str
-    // because they have already been replaced
-    side_selector: String,
-    side_width_selector: String,
-    content_min_selector: String,
+    // the side, side-width and content-min selectors,
+    // a String because they have already been replaced
+    classes_selector: String,
     selector_one: &str,
     selector_two: &str,
     side_width: &str,
     content_min: &str,
+    names: &SelectorNames,
 ) -> String {
+    let sidebar_l = names.tag("sidebar");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        sidebar-l{side_selector}{side_width_selector}{content_min_selector} > {selector_one}:not(outsider-l[layout~="disinherit"]) {{
+        {sidebar_l}{classes_selector} > {selector_one}:not({outsider_l}[{layout}~="disinherit"]) {{
               flex-basis: {side_width};
               flex-grow: 1;
               min-inline-size: initial;
@@ -77,7 +99,7 @@ fn sidebar_group_style(
               min-height:0;
         }}
 
-        sidebar-l{side_selector}{side_width_selector}{content_min_selector} > {selector_two}:not(outsider-l[layout~="disinherit"]) {{
+        {sidebar_l}{classes_selector} > {selector_two}:not({outsider_l}[{layout}~="disinherit"]) {{
                 flex-basis: 0;
                 flex-grow: 999;
                 min-inline-size: {content_min};
@@ -96,40 +118,42 @@ pub fn sidebar_css(
     gap_x: Option<&str>,
     gap_y: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(SIDEBAR_STYLE.to_string());
+    let layout = &names.attribute;
+    set.insert(sidebar_style(names));
     if reverse {
-        set.insert(SIDEBAR_REVERSE_STYLE.to_string());
+        set.insert(sidebar_reverse_style(names));
     }
     if shrink {
-        set.insert(sidebar_shrink_style(reverse));
+        set.insert(sidebar_shrink_style(reverse, names));
     }
     if let Some(value) = gap {
         let harmonic_value = get_harmonic(&value, harmonic_ratio);
-        set.insert(sidebar_gap_style(value, harmonic_value));
+        set.insert(sidebar_gap_style(value, harmonic_value, names));
     }
     if let Some(ref value) = gap_x {
         let harmonic_value = get_harmonic(&value, harmonic_ratio);
-        set.insert(sidebar_gap_x_style(value, harmonic_value));
+        set.insert(sidebar_gap_x_style(value, harmonic_value, names));
     }
     if let Some(ref value) = gap_y {
         let harmonic_value = get_harmonic(&value, harmonic_ratio);
-        set.insert(sidebar_gap_y_style(value, harmonic_value));
+        set.insert(sidebar_gap_y_style(value, harmonic_value, names));
     }
     if side.is_some() || side_width.is_some() || content_min.is_some() {
         let side_width_selector = if let Some(ref val) = side_width {
-            formatdoc!(r#"[layout*="side-width:{}"]"#, val)
+            formatdoc!(r#"[{layout}*="side-width:{}"]"#, val)
         } else {
             "".to_string()
         };
         let content_min_selector = if let Some(ref val) = content_min {
-            formatdoc!(r#"[layout*="content-min:{}"]"#, val)
+            formatdoc!(r#"[{layout}*="content-min:{}"]"#, val)
         } else {
             "".to_string()
         };
         let side_selector = if let Some(ref val) = side {
-            formatdoc!(r#"[layout*="side:{}"]"#, val)
+            formatdoc!(r#"[{layout}*="side:{}"]"#, val)
         } else {
             "".to_string()
         };
@@ -156,13 +180,12 @@ pub fn sidebar_css(
         };
 
         set.insert(sidebar_group_style(
-            side_selector,
-            side_width_selector,
-            content_min_selector,
+            format!("{side_selector}{side_width_selector}{content_min_selector}"),
             selector_one,
             selector_two,
             side_width,
             content_min,
+            names,
         ));
     }
 }
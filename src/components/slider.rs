@@ -1,56 +1,78 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 use std::collections::HashSet;
-const SLIDER_STYLE: &str = r#"
-  slider-l{
+fn slider_style(names: &SelectorNames) -> String {
+    let slider_l = names.tag("slider");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {slider_l}{{
     display: flex;
     block-size: auto;
     overflow-x: auto;
     overflow-y: hidden;
-  }
+  }}
 
-  slider-l > *:not(outsider-l[layout~="disinherit"]) {
+  {slider_l} > *:not({outsider_l}[{layout}~="disinherit"]) {{
       flex-shrink: 0;
       flex-grow: 0;
       height: auto;
       min-width: 0px;
-  }
+  }}
 
-  slider-l > img{
+  {slider_l} > img{{
       object-fit: cover;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const SLIDER_HIDE_BAR_STYLE: &str = r#"
-  slider-l[layout~="hide-bar"]{
+fn slider_hide_bar_style(names: &SelectorNames) -> String {
+    let slider_l = names.tag("slider");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {slider_l}[{layout}~="hide-bar"]{{
     overflow: hidden;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn slider_item_width_style(value: &str) -> String {
+fn slider_item_width_style(value: &str, names: &SelectorNames) -> String {
+    let slider_l = names.tag("slider");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        slider-l[layout~="item-width:{value}"] > *:not(outsider-l[layout~="disinherit"]){{
+        {slider_l}[{layout}~="item-width:{value}"] > *:not({outsider_l}[{layout}~="disinherit"]){{
             flex-basis:{value};
         }}
         "#,
     )
 }
 
-fn slider_height_style(value: &str) -> String {
+fn slider_height_style(value: &str, names: &SelectorNames) -> String {
+    let slider_l = names.tag("slider");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        slider-l[layout~="height:{value}"] > *:not(outsider-l[layout~="disinherit"]){{
+        {slider_l}[{layout}~="height:{value}"] > *:not({outsider_l}[{layout}~="disinherit"]){{
             block-size:{value};
         }}
         "#,
     )
 }
 
-fn slider_gap_style(value: &str, harmonic: String) -> String {
+fn slider_gap_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let slider_l = names.tag("slider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        slider-l[layout~="gap:{value}"] {{
+        {slider_l}[{layout}~="gap:{value}"] {{
             gap: {harmonic};
         }}
         "#,
@@ -63,21 +85,22 @@ pub fn slider_css(
     height: Option<&str>,
     gap: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(SLIDER_STYLE.to_string());
+    set.insert(slider_style(names));
     if hide_bar {
-        set.insert(SLIDER_HIDE_BAR_STYLE.to_string());
+        set.insert(slider_hide_bar_style(names));
     }
     if let Some(value) = item_width {
-        set.insert(slider_item_width_style(value));
+        set.insert(slider_item_width_style(value, names));
     }
     if let Some(value) = height {
-        set.insert(slider_height_style(value));
+        set.insert(slider_height_style(value, names));
     }
 
     if let Some(value) = gap {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(slider_gap_style(value, harmonic_value));
+        set.insert(slider_gap_style(value, harmonic_value, names));
     }
 }
@@ -1,31 +1,43 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
 use std::collections::HashSet;
-const STACK_STYLE: &str = r#"
-  stack-l{
+fn stack_style(names: &SelectorNames) -> String {
+    let stack_l = names.tag("stack");
+    format!(
+        r#"
+  {stack_l}{{
     display: block;
-  }
+  }}
 
-  stack-l > *{
+  {stack_l} > *{{
     margin-block: 0;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn stack_gap_style(value: &str, harmonic: String) -> String {
+fn stack_gap_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let stack_l = names.tag("stack");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        stack-l[layout~="gap:{value}"] > * + *:not(outsider-l[layout~="disinherit"]){{
+        {stack_l}[{layout}~="gap:{value}"] > * + *:not({outsider_l}[{layout}~="disinherit"]){{
             margin-block-start: {harmonic};
         }}
         "#,
     )
 }
 
-fn stack_recursive_style(harmonic: String) -> String {
+fn stack_recursive_style(harmonic: String, names: &SelectorNames) -> String {
+    let stack_l = names.tag("stack");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        stack-l[layout~="recursive"] * + *:not(outsider-l[layout~="disinherit"]){{
+        {stack_l}[{layout}~="recursive"] * + *:not({outsider_l}[{layout}~="disinherit"]){{
             margin-block-start: {harmonic};
         }}
         "#,
@@ -36,14 +48,15 @@ pub fn stack_css(
     gap: Option<&str>,
     recursive: bool,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(STACK_STYLE.to_string());
+    set.insert(stack_style(names));
     if let Some(value) = gap {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(stack_gap_style(value, harmonic_value.clone()));
+        set.insert(stack_gap_style(value, harmonic_value.clone(), names));
         if recursive {
-            set.insert(stack_recursive_style(harmonic_value));
+            set.insert(stack_recursive_style(harmonic_value, names));
         }
     }
 }
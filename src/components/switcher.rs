@@ -1,69 +1,95 @@
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 
 use std::collections::HashSet;
-const SWITCHER_STYLE: &str = r#"
- switcher-l{
+fn switcher_style(names: &SelectorNames) -> String {
+    let switcher_l = names.tag("switcher");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
+    format!(
+        r#"
+ {switcher_l}{{
     display: flex;
     flex-wrap: wrap;
-  }
+  }}
 
-  switcher-l > *:not(outsider-l[layout~="disinherit"]){
+  {switcher_l} > *:not({outsider_l}[{layout}~="disinherit"]){{
       flex-grow: 1;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-const SWITCHER_REVERSE_STYLE: &str = r#"
-  switcher-l[layout~="reverse"]{
+fn switcher_reverse_style(names: &SelectorNames) -> String {
+    let switcher_l = names.tag("switcher");
+    let layout = &names.attribute;
+    format!(
+        r#"
+  {switcher_l}[{layout}~="reverse"]{{
     flex-wrap: wrap-reverse;
-  }
-"#;
+  }}
+"#
+    )
+}
 
-fn switcher_threshold_style(value: &str) -> String {
+fn switcher_threshold_style(value: &str, names: &SelectorNames) -> String {
+    let switcher_l = names.tag("switcher");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        switcher-l[layout~="threshold:{value}"] > *:not(outsider-l[layout~="disinherit"]) {{
+        {switcher_l}[{layout}~="threshold:{value}"] > *:not({outsider_l}[{layout}~="disinherit"]) {{
             flex-basis: calc(({value} - 100%) * 999);
         }}
         "#,
     )
 }
 
-fn switcher_limit_style(value: &str) -> String {
+fn switcher_limit_style(value: &str, names: &SelectorNames) -> String {
+    let switcher_l = names.tag("switcher");
+    let outsider_l = names.tag("outsider");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        switcher-l[layout~="limit:{value}"] > :nth-last-child(n+{value}):not(outsider-l[layout~="disinherit"]),
-        switcher-l[layout~="limit:{value}"] > :nth-last-child(n+{value}) ~ *:not(outsider-l[layout~="disinherit"]){{
+        {switcher_l}[{layout}~="limit:{value}"] > :nth-last-child(n+{value}):not({outsider_l}[{layout}~="disinherit"]),
+        {switcher_l}[{layout}~="limit:{value}"] > :nth-last-child(n+{value}) ~ *:not({outsider_l}[{layout}~="disinherit"]){{
             flex-basis: 100%;
         }}
         "#,
     )
 }
 
-fn switcher_gap_style(value: &str, harmonic: String) -> String {
+fn switcher_gap_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let switcher_l = names.tag("switcher");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        switcher-l[layout~="gap:{value}"]{{
+        {switcher_l}[{layout}~="gap:{value}"]{{
             gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn switcher_gap_x_style(value: &str, harmonic: String) -> String {
+fn switcher_gap_x_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let switcher_l = names.tag("switcher");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        switcher-l[layout~="gap-x:{value}"]{{
+        {switcher_l}[{layout}~="gap-x:{value}"]{{
             column-gap: {harmonic};
         }}
         "#,
     )
 }
 
-fn switcher_gap_y_style(value: &str, harmonic: String) -> String {
+fn switcher_gap_y_style(value: &str, harmonic: String, names: &SelectorNames) -> String {
+    let switcher_l = names.tag("switcher");
+    let layout = &names.attribute;
     formatdoc!(
         r#"
-        switcher-l[layout~="gap-y:{value}"]{{
+        {switcher_l}[{layout}~="gap-y:{value}"]{{
             row-gap: {harmonic};
         }}
         "#,
@@ -78,28 +104,29 @@ pub fn switcher_css(
     gap_x: Option<&str>,
     gap_y: Option<&str>,
     harmonic_ratio: f64,
+    names: &SelectorNames,
     set: &mut HashSet<String>,
 ) {
-    set.insert(SWITCHER_STYLE.to_string());
+    set.insert(switcher_style(names));
     if let Some(value) = threshold {
-        set.insert(switcher_threshold_style(value));
+        set.insert(switcher_threshold_style(value, names));
     }
     if let Some(value) = limit {
-        set.insert(switcher_limit_style(value));
+        set.insert(switcher_limit_style(value, names));
     }
     if reverse {
-        set.insert(SWITCHER_REVERSE_STYLE.to_string());
+        set.insert(switcher_reverse_style(names));
     }
     if let Some(value) = gap {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(switcher_gap_style(value, harmonic_value));
+        set.insert(switcher_gap_style(value, harmonic_value, names));
     }
     if let Some(ref value) = gap_x {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(switcher_gap_x_style(value, harmonic_value));
+        set.insert(switcher_gap_x_style(value, harmonic_value, names));
     }
     if let Some(ref value) = gap_y {
         let harmonic_value = get_harmonic(value, harmonic_ratio);
-        set.insert(switcher_gap_y_style(value, harmonic_value));
+        set.insert(switcher_gap_y_style(value, harmonic_value, names));
     }
 }
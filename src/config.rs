@@ -1,11 +1,408 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
 
+use crate::naming::ElementNaming;
+
+/// Name of the configuration file looked up by [`LayoutStyleConfig::discover`].
+pub const CONFIG_FILE_NAME: &str = "layoutcss.toml";
 
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct LayoutStyleConfig {
     pub harmonic_ratio: f64,
     pub base_value: String,
     pub dev: bool,
+    /// write the stylesheet without whitespace, for production builds
+    pub minify: bool,
+    /// `layoutNNNpx` attributes apply from NNNpx up instead of up to NNNpx
+    pub mobile_first: bool,
+    /// named breakpoints usable as `layout-<name>` attributes, with their width in px
+    pub breakpoints: BTreeMap<String, usize>,
+    /// name of the layout attribute, like `data-layout` to keep the html valid,
+    /// the breakpoint attributes are named after it (`data-layout600px`)
+    pub attribute_name: String,
+    /// how the components are named, `{name}` is replaced by the name
+    /// of the component: `{name}-l` gives `row-l`, `lc-{name}` gives `lc-row`
+    pub element_name: String,
+    /// read the components and their classes from the `class` attribute,
+    /// like `<div class="row-l gap:2">`, for markup that can't use custom elements.
+    /// The prefixed form `class="l-row l-gap-2"` is not read, it's reported.
+    pub class_mode: bool,
+    /// wrap the reset, the components, the utilities and the breakpoint overrides
+    /// in the `layoutcss.*` cascade layers, so they can be ordered with other styles
+    pub layers: bool,
+    /// wrap the selectors in `:where()` so the styles of the page always override them
+    pub low_specificity: bool,
+}
+
+impl Default for LayoutStyleConfig {
+    fn default() -> Self {
+        LayoutStyleConfig {
+            harmonic_ratio: 1.618,
+            base_value: "16px".to_string(),
+            dev: false,
+            minify: false,
+            mobile_first: false,
+            breakpoints: BTreeMap::new(),
+            attribute_name: "layout".to_string(),
+            element_name: "{name}-l".to_string(),
+            class_mode: false,
+            layers: false,
+            low_specificity: false,
+        }
+    }
+}
+
+/// Same shape as LayoutStyleConfig but every field is optional
+/// and keeps its position in the file, so we can fill the defaults
+/// and point at the right line when a value is invalid.
+#[derive(Debug, Deserialize)]
+struct RawLayoutStyleConfig {
+    harmonic_ratio: Option<Spanned<f64>>,
+    base_value: Option<Spanned<String>>,
+    dev: Option<bool>,
+    minify: Option<bool>,
+    mobile_first: Option<bool>,
+    breakpoints: Option<BTreeMap<String, Spanned<usize>>>,
+    attribute_name: Option<Spanned<String>>,
+    element_name: Option<Spanned<String>>,
+    class_mode: Option<bool>,
+    layers: Option<bool>,
+    low_specificity: Option<bool>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file couldn't be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The file is not valid toml or a value has the wrong type.
+    Parse { line: Option<usize>, message: String },
+    /// The value of `key` is well typed but not acceptable.
+    InvalidValue {
+        key: &'static str,
+        line: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "cannot read {}: {source}", path.display()),
+            Self::Parse {
+                line: Some(line),
+                message,
+            } => write!(f, "invalid configuration at line {line}: {message}"),
+            Self::Parse { line: None, message } => write!(f, "invalid configuration: {message}"),
+            Self::InvalidValue { key, line, message } => {
+                write!(f, "invalid value for `{key}` at line {line}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// return the line (starting at 1) of the byte at `index` in `text`
+fn line_of(text: &str, index: usize) -> usize {
+    text[..index.min(text.len())].matches('\n').count() + 1
+}
+
+/// return true if the value is a number followed by a css unit, like `16px` or `1.2rem`.
+/// `0` is the only length accepted without unit.
+pub fn is_css_length(value: &str) -> bool {
+    const UNITS: [&str; 19] = [
+        "px", "rem", "em", "%", "pt", "pc", "cm", "mm", "in", "q", "vw", "vh", "vmin", "vmax",
+        "ch", "ex", "cqw", "cqh", "lh",
+    ];
+    let value = value.trim();
+    if value == "0" {
+        return true;
+    }
+    let number_end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(number_end);
+    number.parse::<f64>().is_ok() && UNITS.contains(&unit.to_ascii_lowercase().as_str())
+}
+
+/// return true if the name can follow `layout-` in an attribute name
+fn is_breakpoint_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// return true if the name can be used as the layout attribute,
+/// it must start with a letter to be a valid html attribute
+fn is_attribute_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic()) && is_breakpoint_name(name)
 }
 
+impl LayoutStyleConfig {
+    /// Parse and validate the content of a `layoutcss.toml` file,
+    /// missing keys get their default value.
+    pub fn from_toml_str(text: &str) -> Result<Self, ConfigError> {
+        let raw: RawLayoutStyleConfig = toml::from_str(text).map_err(|e| ConfigError::Parse {
+            line: e.span().map(|span| line_of(text, span.start)),
+            message: e.message().to_string(),
+        })?;
+        let mut config = LayoutStyleConfig::default();
 
+        if let Some(harmonic_ratio) = raw.harmonic_ratio {
+            let value = *harmonic_ratio.get_ref();
+            if !value.is_finite() || value <= 0.0 {
+                return Err(ConfigError::InvalidValue {
+                    key: "harmonic_ratio",
+                    line: line_of(text, harmonic_ratio.span().start),
+                    message: format!("expected a positive number, got {value}"),
+                });
+            }
+            config.harmonic_ratio = harmonic_ratio.into_inner();
+        }
+        if let Some(base_value) = raw.base_value {
+            if !is_css_length(base_value.get_ref()) {
+                return Err(ConfigError::InvalidValue {
+                    key: "base_value",
+                    line: line_of(text, base_value.span().start),
+                    message: format!("expected a css length like `16px`, got `{}`", base_value.get_ref()),
+                });
+            }
+            config.base_value = base_value.into_inner();
+        }
+        if let Some(dev) = raw.dev {
+            config.dev = dev;
+        }
+        if let Some(minify) = raw.minify {
+            config.minify = minify;
+        }
+        if let Some(mobile_first) = raw.mobile_first {
+            config.mobile_first = mobile_first;
+        }
+        for (name, width) in raw.breakpoints.unwrap_or_default() {
+            let line = line_of(text, width.span().start);
+            if !is_breakpoint_name(&name) {
+                return Err(ConfigError::InvalidValue {
+                    key: "breakpoints",
+                    line,
+                    message: format!(
+                        "`{name}` can't be used in an attribute name, use letters, digits, `-` or `_`"
+                    ),
+                });
+            }
+            if *width.get_ref() == 0 {
+                return Err(ConfigError::InvalidValue {
+                    key: "breakpoints",
+                    line,
+                    message: format!("the width of `{name}` must be a positive number of px"),
+                });
+            }
+            config.breakpoints.insert(name, width.into_inner());
+        }
+        if let Some(attribute_name) = raw.attribute_name {
+            if !is_attribute_name(attribute_name.get_ref()) {
+                return Err(ConfigError::InvalidValue {
+                    key: "attribute_name",
+                    line: line_of(text, attribute_name.span().start),
+                    message: format!(
+                        "`{}` is not a valid attribute name, use letters, digits, `-` or `_`",
+                        attribute_name.get_ref()
+                    ),
+                });
+            }
+            config.attribute_name = attribute_name.into_inner();
+        }
+        if let Some(element_name) = raw.element_name {
+            if ElementNaming::new(element_name.get_ref()).is_none() {
+                return Err(ConfigError::InvalidValue {
+                    key: "element_name",
+                    line: line_of(text, element_name.span().start),
+                    message: format!(
+                        "expected a pattern like `lc-{{name}}` giving lowercase names with a `-`, got `{}`",
+                        element_name.get_ref()
+                    ),
+                });
+            }
+            config.element_name = element_name.into_inner();
+        }
+        if let Some(class_mode) = raw.class_mode {
+            config.class_mode = class_mode;
+        }
+        if let Some(layers) = raw.layers {
+            config.layers = layers;
+        }
+        if let Some(low_specificity) = raw.low_specificity {
+            config.low_specificity = low_specificity;
+        }
+        Ok(config)
+    }
+
+    /// Read and validate the configuration file at `path`.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Look for a `layoutcss.toml` in `dir` and its parents and load the first one found.
+    /// If there is none, the default configuration is returned.
+    pub fn discover(dir: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        match find_config_file(dir) {
+            Some(path) => Self::from_path(path),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// return the path of the closest `layoutcss.toml`, starting from `dir`
+/// and walking up to the root of the filesystem.
+pub fn find_config_file(dir: impl AsRef<Path>) -> Option<PathBuf> {
+    dir.as_ref()
+        .ancestors()
+        .map(|ancestor| ancestor.join(CONFIG_FILE_NAME))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_keys_get_default_values() {
+        let config = LayoutStyleConfig::from_toml_str("dev = true").unwrap();
+        assert_eq!(config.harmonic_ratio, 1.618);
+        assert_eq!(config.base_value, "16px");
+        assert!(config.dev);
+        assert!(!config.minify);
+        assert!(!config.mobile_first);
+    }
+
+    #[test]
+    fn non_positive_harmonic_ratio_is_reported_with_its_line() {
+        let error = LayoutStyleConfig::from_toml_str("dev = false\nharmonic_ratio = -2.0\n");
+        match error {
+            Err(ConfigError::InvalidValue { key, line, .. }) => {
+                assert_eq!(key, "harmonic_ratio");
+                assert_eq!(line, 2);
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn infinite_harmonic_ratio_is_reported() {
+        let error = LayoutStyleConfig::from_toml_str("harmonic_ratio = inf\n");
+        match error {
+            Err(ConfigError::InvalidValue { key, line, .. }) => {
+                assert_eq!(key, "harmonic_ratio");
+                assert_eq!(line, 1);
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn base_value_without_unit_is_reported_with_its_line() {
+        let error = LayoutStyleConfig::from_toml_str("\n\nbase_value = \"16\"\n");
+        match error {
+            Err(ConfigError::InvalidValue { key, line, .. }) => {
+                assert_eq!(key, "base_value");
+                assert_eq!(line, 3);
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn breakpoints_are_read_from_their_table() {
+        let config =
+            LayoutStyleConfig::from_toml_str("[breakpoints]\nsm = 480\nmd = 768\n").unwrap();
+        assert_eq!(config.breakpoints.get("sm"), Some(&480));
+        assert_eq!(config.breakpoints.get("md"), Some(&768));
+
+        let error = LayoutStyleConfig::from_toml_str("[breakpoints]\nsm = 480\n\"m d\" = 768\n");
+        assert!(matches!(
+            error,
+            Err(ConfigError::InvalidValue {
+                key: "breakpoints",
+                line: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn attribute_name_must_be_a_valid_attribute() {
+        let config = LayoutStyleConfig::from_toml_str("attribute_name = \"data-layout\"").unwrap();
+        assert_eq!(config.attribute_name, "data-layout");
+        let error = LayoutStyleConfig::from_toml_str("attribute_name = \"[layout]\"");
+        assert!(matches!(
+            error,
+            Err(ConfigError::InvalidValue {
+                key: "attribute_name",
+                line: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn element_name_must_contain_the_name_placeholder() {
+        let config = LayoutStyleConfig::from_toml_str("element_name = \"x-{name}\"").unwrap();
+        assert_eq!(config.element_name, "x-{name}");
+        let error = LayoutStyleConfig::from_toml_str("element_name = \"x-row\"");
+        assert!(matches!(
+            error,
+            Err(ConfigError::InvalidValue {
+                key: "element_name",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn wrong_type_is_a_parse_error() {
+        let error = LayoutStyleConfig::from_toml_str("dev = \"yes\"");
+        assert!(matches!(error, Err(ConfigError::Parse { line: Some(1), .. })));
+    }
+
+    #[test]
+    fn css_lengths() {
+        assert!(is_css_length("16px"));
+        assert!(is_css_length("1.2rem"));
+        assert!(is_css_length("100%"));
+        assert!(is_css_length("0"));
+        assert!(!is_css_length("16"));
+        assert!(!is_css_length("px"));
+        assert!(!is_css_length("16 px"));
+    }
+
+    #[test]
+    fn discover_walks_up_to_find_the_config_file() {
+        let root = std::env::temp_dir().join(format!("layoutcss-discover-{}", std::process::id()));
+        let nested = root.join("templates").join("partials");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join(CONFIG_FILE_NAME), "base_value = \"10px\"").unwrap();
+
+        let config = LayoutStyleConfig::discover(&nested).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(config.base_value, "10px");
+        assert_eq!(config.harmonic_ratio, 1.618);
+    }
+}
@@ -1,18 +1,13 @@
-pub const DEV_CSS: &str = r#"
-area-l,
-center-l,
-box-l,
-extender-l,
-grid-l,
-icon-l,
-row-l,
-outsider-l,
-rack-l,
-sidebar-l,
-slider-l,
-stack-l,
-switcher-l,
-ol,
+use crate::naming::SelectorNames;
+
+/// Components outlined in dev mode, before the elements of DEV_ELEMENTS_CSS.
+const DEV_COMPONENTS: [&str; 13] = [
+    "area", "center", "box", "extender", "grid", "icon", "row", "outsider", "rack", "sidebar",
+    "slider", "stack", "switcher",
+];
+
+/// Css outlining the elements in dev mode, the components are added before them.
+pub const DEV_ELEMENTS_CSS: &str = r#"ol,
 ul,
 dl,
 div,
@@ -40,3 +35,12 @@ select {
     background-color: gray;
 }
 "#;
+
+/// return the css of the dev mode, outlining the components and the usual elements
+pub fn dev_css(names: &SelectorNames) -> String {
+    let components: String = DEV_COMPONENTS
+        .iter()
+        .map(|component_name| format!("{},\n", names.tag(component_name)))
+        .collect();
+    format!("\n{components}{DEV_ELEMENTS_CSS}")
+}
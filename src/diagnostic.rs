@@ -0,0 +1,137 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::span::Span;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DiagnosticKind {
+    /// The token doesn't match any layout class, like `max-widht:60ch` or `gap2`.
+    UnknownClass,
+    /// The class exists but has no effect on this element,
+    /// like `max-cols:3` on a `row-l`.
+    NotApplicable,
+    /// The class expects a value but nothing was written after the `:`.
+    MissingValue,
+    /// The class expects a number of the harmonic scale or a css length,
+    /// like `gap:2x`.
+    InvalidValue,
+    /// The attribute starts with the layout `attribute` but is not a valid
+    /// breakpoint attribute, like `layout600` or `layout@600px`.
+    MalformedBreakpoint { attribute: String },
+    /// The attribute uses a breakpoint name, like `layout-xl`,
+    /// which is not declared in the `[breakpoints]` of the config.
+    UnknownBreakpoint,
+    /// The breakpoint attribute, like `layout600px`, does nothing in class mode
+    /// where the classes are read from the class attribute.
+    IgnoredInClassMode,
+    /// The class is written in the prefixed form, like `l-gap-2`,
+    /// which class mode doesn't read, `class` is the form it reads.
+    PrefixedClass { class: String },
+    /// The value of a class set from rust can't be written in an attribute,
+    /// because it has whitespace or a `'`.
+    UnwritableValue,
+}
+
+/// A problem found in the markup while parsing it,
+/// the css is still generated without the faulty part.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// The tag on which the problem has been found.
+    pub tag_name: String,
+    /// The class or the attribute name responsible for the diagnostic.
+    pub token: String,
+    /// Where the token is in the parsed text, if it comes from a text.
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    pub fn new(kind: DiagnosticKind, tag_name: &str, token: &str) -> Self {
+        Diagnostic {
+            kind,
+            tag_name: tag_name.to_string(),
+            token: token.to_string(),
+            span: None,
+        }
+    }
+
+    /// return the diagnostic after the file it comes from,
+    /// like `a.html:3:5: unknown layout class ...` or `a.html: unknown layout class ...`
+    pub fn in_file(&self, file: &Path) -> String {
+        match self.span {
+            Some(_) => format!("{}:{self}", file.display()),
+            None => format!("{}: {self}", file.display()),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Diagnostic {
+            kind,
+            tag_name,
+            token,
+            span,
+        } = self;
+        if let Some(span) = span {
+            write!(f, "{}:{}: ", span.line, span.column)?;
+        }
+        match kind {
+            DiagnosticKind::UnknownClass => write!(f, "unknown layout class `{token}` on <{tag_name}>"),
+            DiagnosticKind::NotApplicable => {
+                write!(f, "layout class `{token}` has no effect on <{tag_name}>")
+            }
+            DiagnosticKind::MissingValue => {
+                write!(f, "layout class `{token}` on <{tag_name}> expects a value after `:`")
+            }
+            DiagnosticKind::InvalidValue => write!(
+                f,
+                "layout class `{token}` on <{tag_name}> expects a number or a css length"
+            ),
+            DiagnosticKind::MalformedBreakpoint { attribute } => write!(
+                f,
+                "malformed breakpoint attribute `{token}` on <{tag_name}>, expected something like `{attribute}600px`"
+            ),
+            DiagnosticKind::UnknownBreakpoint => write!(
+                f,
+                "unknown breakpoint `{token}` on <{tag_name}>, it must be declared in [breakpoints]"
+            ),
+            DiagnosticKind::IgnoredInClassMode => write!(
+                f,
+                "breakpoint attribute `{token}` on <{tag_name}> is ignored in class mode"
+            ),
+            DiagnosticKind::UnwritableValue => write!(
+                f,
+                "layout class `{token}` on <{tag_name}> can't be written in an attribute, its value has whitespace or a `'`"
+            ),
+            DiagnosticKind::PrefixedClass { class } => write!(
+                f,
+                "class `{token}` on <{tag_name}> is not read in class mode, write `{class}` instead"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_file_is_separated_from_the_diagnostic() {
+        let mut diagnostic = Diagnostic::new(DiagnosticKind::UnknownClass, "div", "gap2");
+        assert_eq!(
+            diagnostic.in_file(Path::new("a.html")),
+            "a.html: unknown layout class `gap2` on <div>"
+        );
+        diagnostic.span = Some(Span {
+            start: 12,
+            end: 16,
+            line: 1,
+            column: 13,
+        });
+        assert_eq!(
+            diagnostic.in_file(Path::new("a.html")),
+            "a.html:1:13: unknown layout class `gap2` on <div>"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+/// What the scanner is reading in a javascript expression.
+#[derive(Debug, PartialEq)]
+enum Context {
+    /// code between braces, `{ ... }` or `${ ... }`
+    Braces,
+    /// the static part of a template literal, between backticks
+    Template,
+}
+
+/// return the index of the `}` closing the expression opened by the `{` at `open`,
+/// or the length of the text if the expression is never closed.
+pub fn closing_brace(text: &str, open: usize) -> usize {
+    match scan(&text[open + 1..]).0 {
+        Some(end) => open + 1 + end,
+        None => text.len(),
+    }
+}
+
+/// return the static strings of a javascript expression (without its braces),
+/// with their offset in the expression: string literals, like both branches
+/// of `cond ? "p:1" : "p:2"`, and the static parts of template literals.
+/// In a template literal, the tokens stuck to an interpolation are incomplete,
+/// so `p:${x} gap:1` only gives `gap:1`.
+pub fn string_literals(expression: &str) -> Vec<(usize, &str)> {
+    scan(expression).1
+}
+
+/// return the value of the expression if it's a single string literal, like `"p:1"`,
+/// None if its value is only known at runtime, like `` `p:${x}` `` or `a ? "p:1" : "p:2"`
+pub fn plain_literal(expression: &str) -> Option<&str> {
+    let expression = expression.trim();
+    let quote = expression.chars().next().filter(|c| matches!(c, '"' | '\'' | '`'))?;
+    let literal = expression.strip_prefix(quote)?.strip_suffix(quote)?;
+    let is_dynamic = literal.contains(quote)
+        || literal.contains('\\')
+        || (quote == '`' && literal.contains("${"));
+    (!is_dynamic).then_some(literal)
+}
+
+/// scan the expression until the `}` which is not opened inside of it
+fn scan(text: &str) -> (Option<usize>, Vec<(usize, &str)>) {
+    let bytes = text.as_bytes();
+    let mut literals = vec![];
+    let mut stack = vec![Context::Braces];
+    // start of the static part of the template literal being read,
+    // and whether it starts right after an interpolation
+    let mut part_start = 0;
+    let mut after_interpolation = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        if stack.last() == Some(&Context::Template) {
+            match bytes[i] {
+                b'\\' => i += 1,
+                b'`' => {
+                    push_template_part(
+                        text,
+                        part_start,
+                        i,
+                        after_interpolation,
+                        false,
+                        &mut literals,
+                    );
+                    stack.pop();
+                }
+                b'$' if bytes.get(i + 1) == Some(&b'{') => {
+                    push_template_part(
+                        text,
+                        part_start,
+                        i,
+                        after_interpolation,
+                        true,
+                        &mut literals,
+                    );
+                    stack.push(Context::Braces);
+                    i += 1;
+                }
+                _ => {}
+            }
+        } else {
+            match bytes[i] {
+                b'{' => stack.push(Context::Braces),
+                b'}' => {
+                    stack.pop();
+                    match stack.last() {
+                        None => return (Some(i), literals),
+                        Some(Context::Template) => {
+                            part_start = i + 1;
+                            after_interpolation = true;
+                        }
+                        Some(Context::Braces) => {}
+                    }
+                }
+                quote @ (b'"' | b'\'') => {
+                    let start = i + 1;
+                    i = start;
+                    while i < bytes.len() && bytes[i] != quote {
+                        if bytes[i] == b'\\' {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                    literals.push((start, &text[start..i.min(bytes.len())]));
+                }
+                b'`' => {
+                    stack.push(Context::Template);
+                    part_start = i + 1;
+                    after_interpolation = false;
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    (None, literals)
+}
+
+/// add the part of the template literal between `start` and `end`,
+/// without the tokens stuck to an interpolation
+fn push_template_part<'a>(
+    text: &'a str,
+    start: usize,
+    end: usize,
+    after_interpolation: bool,
+    before_interpolation: bool,
+    literals: &mut Vec<(usize, &'a str)>,
+) {
+    let mut start = start;
+    let mut end = end.min(text.len());
+    let part = &text[start..end];
+    if after_interpolation && !part.starts_with(char::is_whitespace) {
+        start += part.find(char::is_whitespace).unwrap_or(part.len());
+    }
+    let part = &text[start..end];
+    if before_interpolation && !part.ends_with(char::is_whitespace) {
+        end = start + part.rfind(char::is_whitespace).unwrap_or(0);
+    }
+    if !text[start..end].trim().is_empty() {
+        literals.push((start, &text[start..end]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(expression: &str) -> Vec<&str> {
+        string_literals(expression)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    #[test]
+    fn closing_brace_skips_nested_braces_and_strings() {
+        let text = r#"<a layout={cond ? "}" : `${ {a: 1}.a }`}>"#;
+        assert_eq!(closing_brace(text, 10), text.len() - 2);
+        assert_eq!(closing_brace("{ \"p:2\"", 0), 7);
+    }
+
+    #[test]
+    fn string_literals_of_a_ternary() {
+        assert_eq!(
+            values(r#"cond ? "p:1 gap:2" : 'p:2'"#),
+            vec!["p:1 gap:2", "p:2"]
+        );
+    }
+
+    #[test]
+    fn template_literals_keep_their_complete_tokens() {
+        assert_eq!(values("`p:${x} gap:1 ${y}`"), vec![" gap:1 "]);
+        assert_eq!(values("`p:1 ${x}`"), vec!["p:1 "]);
+        assert_eq!(values("`gap:${x}`"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn offsets_point_into_the_expression() {
+        let expression = r#"a ? "p:1" : "p:2""#;
+        for (offset, value) in string_literals(expression) {
+            assert_eq!(&expression[offset..offset + value.len()], value);
+        }
+    }
+
+    #[test]
+    fn plain_literals_are_the_only_static_expressions() {
+        assert_eq!(plain_literal(" \"p:1 gap:2\" "), Some("p:1 gap:2"));
+        assert_eq!(plain_literal("`p:1`"), Some("p:1"));
+        assert_eq!(plain_literal("`${a} gap:1 `"), None);
+        assert_eq!(plain_literal("a ? \"p:1\" : \"p:2\""), None);
+        assert_eq!(plain_literal("\"p:1\" + \"p:2\""), None);
+    }
+}
@@ -0,0 +1,192 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the files read when a directory is given as input.
+pub const TEMPLATE_EXTENSIONS: [&str; 19] = [
+    "html", "htm", "xhtml", "jinja", "jinja2", "j2", "twig", "hbs", "handlebars", "njk",
+    "liquid", "erb", "ejs", "php", "vue", "svelte", "astro", "jsx", "tsx",
+];
+
+/// Directories never walked into, because they don't contain
+/// templates written by hand and they can be huge.
+const IGNORED_DIRECTORIES: [&str; 3] = ["node_modules", "target", "dist"];
+
+/// return true if the path has one of the `extensions`
+pub fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extensions.iter().any(|e| e.eq_ignore_ascii_case(extension)))
+}
+
+/// return every file inside `dir` (recursively) having one of the `extensions`, sorted by path.
+/// Hidden directories and directories like `node_modules` are skipped.
+pub fn walk_dir(dir: &Path, extensions: &[&str]) -> io::Result<Vec<PathBuf>> {
+    walk(dir, &|path| has_extension(path, extensions))
+}
+
+fn walk(dir: &Path, keep: &dyn Fn(&Path) -> bool) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            // the type of the entry itself, a symlink is not followed
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if file_type.is_dir() {
+                if !name.starts_with('.') && !IGNORED_DIRECTORIES.contains(&name) {
+                    dirs.push(path);
+                }
+            } else if path.is_file() && keep(&path) {
+                // a symlinked directory is not walked, it could point to one of its parents
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// return true if the pattern contains a glob wildcard
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Match a `/` separated path against a glob pattern.
+/// `*` matches anything inside a path component, `?` a single character,
+/// `**` any number of components and `[abc]` one of the characters.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').filter(|c| !c.is_empty() && *c != ".").collect();
+    let path: Vec<&str> = path.split('/').filter(|c| !c.is_empty() && *c != ".").collect();
+    match_components(&pattern, &path)
+}
+
+fn match_components(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_components(rest, &path[skip..])),
+        Some((component, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                let component: Vec<char> = component.chars().collect();
+                let name: Vec<char> = name.chars().collect();
+                match_component(&component, &name) && match_components(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_component(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| match_component(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && match_component(rest, &name[1..]),
+        Some(('[', rest)) => {
+            let Some(close) = rest.iter().position(|c| *c == ']') else {
+                return name.first() == Some(&'[') && match_component(rest, &name[1..]);
+            };
+            match name.split_first() {
+                Some((c, name_rest)) => rest[..close].contains(c) && match_component(&rest[close + 1..], name_rest),
+                None => false,
+            }
+        }
+        Some((c, rest)) => name.first() == Some(c) && match_component(rest, &name[1..]),
+    }
+}
+
+/// Expand the inputs of the command line into a sorted list of files without duplicates:
+/// files are kept as they are, directories are walked to find templates
+/// and globs are matched against the files of their directory.
+pub fn collect_files(inputs: &[String]) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for input in inputs {
+        if is_glob(input) {
+            // the part of the pattern before the first wildcard is the directory to walk
+            let base = input
+                .split('/')
+                .take_while(|component| !is_glob(component))
+                .collect::<Vec<&str>>()
+                .join("/");
+            let base = if base.is_empty() { ".".to_string() } else { base };
+            // a glob can explicitly target any kind of file
+            for file in walk(Path::new(&base), &|_| true)? {
+                let relative = file.strip_prefix(".").unwrap_or(&file);
+                if glob_match(input, &relative.to_string_lossy()) {
+                    files.push(file);
+                }
+            }
+        } else {
+            let path = PathBuf::from(input);
+            if path.is_dir() {
+                files.extend(walk_dir(&path, &TEMPLATE_EXTENSIONS)?);
+            } else if path.is_file() {
+                files.push(path);
+            } else {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{input}: no such file or directory"),
+                ));
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_star_stays_inside_a_component() {
+        assert!(glob_match("templates/*.html", "templates/index.html"));
+        assert!(!glob_match("templates/*.html", "templates/blog/index.html"));
+    }
+
+    #[test]
+    fn glob_double_star_crosses_components() {
+        assert!(glob_match("templates/**/*.html", "templates/index.html"));
+        assert!(glob_match("templates/**/*.html", "templates/blog/post/index.html"));
+        assert!(glob_match("./templates/**/*.html", "templates/blog/index.html"));
+        assert!(!glob_match("templates/**/*.html", "src/index.html"));
+    }
+
+    #[test]
+    fn glob_question_mark_and_classes() {
+        assert!(glob_match("page?.[jt]sx", "page1.tsx"));
+        assert!(!glob_match("page?.[jt]sx", "page12.tsx"));
+        assert!(!glob_match("page?.[jt]sx", "page1.csx"));
+    }
+
+    #[test]
+    fn extensions_are_case_insensitive() {
+        assert!(has_extension(Path::new("index.HTML"), &TEMPLATE_EXTENSIONS));
+        assert!(!has_extension(Path::new("style.css"), &TEMPLATE_EXTENSIONS));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directories_are_not_walked() {
+        let root = std::env::temp_dir().join(format!("layoutcss-walk-{}", std::process::id()));
+        let templates = root.join("templates");
+        fs::create_dir_all(&templates).unwrap();
+        fs::write(templates.join("index.html"), "").unwrap();
+        fs::write(root.join("base.html"), "").unwrap();
+        std::os::unix::fs::symlink("..", templates.join("loop")).unwrap();
+        std::os::unix::fs::symlink(root.join("base.html"), templates.join("base.html")).unwrap();
+
+        let files = walk_dir(&root, &TEMPLATE_EXTENSIONS);
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(
+            files.unwrap(),
+            vec![
+                root.join("base.html"),
+                templates.join("base.html"),
+                templates.join("index.html")
+            ]
+        );
+    }
+}
@@ -1,3 +1,15 @@
+use crate::config::is_css_length;
+
+/// Return true if the value can be given to get_harmonic: a number of the harmonic scale,
+/// a css length, a css variable (`--name`), a css function like `calc(...)` or `none`.
+pub fn is_harmonic_value(value: &str) -> bool {
+    value.starts_with("--")
+        || value == "none"
+        || value.parse::<f64>().is_ok_and(f64::is_finite)
+        || is_css_length(value)
+        || (value.contains('(') && value.ends_with(')'))
+}
+
 pub fn get_harmonic(value: &str, harmonic: f64) -> String {
     // if its a css variable
     if value.starts_with("--") {
@@ -54,6 +66,16 @@ mod tests {
         assert_eq!(get_harmonic("invalid", HARMONIC), "invalid");
     }
 
+    #[test]
+    fn harmonic_values_are_recognized() {
+        for value in ["2", "-1.5", "16px", "60%", "--gap", "none", "calc(1rem+2px)"] {
+            assert!(is_harmonic_value(value), "{value}");
+        }
+        for value in ["2x", "big", "1.5.2", ""] {
+            assert!(!is_harmonic_value(value), "{value}");
+        }
+    }
+
     #[test]
     fn test_empty_string() {
         // Test for empty string, should return an empty string as is
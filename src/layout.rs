@@ -0,0 +1,843 @@
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use crate::builder::LayoutElement;
+use crate::config::LayoutStyleConfig;
+use crate::diagnostic::{Diagnostic, DiagnosticKind};
+use crate::media_query::MediaQuery;
+use crate::naming::ElementNaming;
+use crate::parser::Parser;
+use crate::{generate_final_css_with_config, insert_css_rules};
+
+/// Layout of an element described in rust instead of markup, like
+/// `Layout::row().gap("2").at(600, |row| row.direction("column"))`
+/// for `<row-l layout="gap:2" layout600px="direction:column">`.
+/// The elements and the css are generated by parsing its opening tag,
+/// so they are always the ones a template with the same attributes gets.
+///
+/// `C` is the component of the element, each component only has
+/// the setters of its classes, and every element has the utilities:
+///
+/// ```
+/// use layoutcss_parser::layout::Layout;
+///
+/// let row = Layout::row().gap("2").nowrap().p("1");
+/// ```
+///
+/// ```compile_fail
+/// use layoutcss_parser::layout::Layout;
+///
+/// let row = Layout::row().scale("2");
+/// ```
+///
+/// The values are not checked by the types, the ones which can't be written
+/// in an attribute (with whitespace or a `'`) are left out and reported
+/// by `diagnostics`, like the classes of the markup with a wrong value.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Layout<C = Element> {
+    /// default tag name of the component, None for an element only using utilities
+    component: Option<&'static str>,
+    classes: Vec<String>,
+    /// classes of the breakpoint attributes, by breakpoint in px
+    breakpoints: Vec<(usize, Vec<String>)>,
+    /// classes left out because their value can't be written in an attribute
+    rejected: Vec<String>,
+    component_kind: PhantomData<C>,
+}
+
+/// An element which is not a component, it only has the utilities.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Element;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AreaL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BoxL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CenterL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExtenderL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GridL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IconL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RowL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OutsiderL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RackL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SidebarL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SliderL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StackL;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SwitcherL;
+
+/// components having the `gap` class
+pub trait Gap {}
+impl Gap for AreaL {}
+impl Gap for GridL {}
+impl Gap for IconL {}
+impl Gap for RowL {}
+impl Gap for RackL {}
+impl Gap for SidebarL {}
+impl Gap for SliderL {}
+impl Gap for StackL {}
+impl Gap for SwitcherL {}
+
+/// components having the `gap-x` and `gap-y` classes
+pub trait GapXY {}
+impl GapXY for AreaL {}
+impl GapXY for GridL {}
+impl GapXY for RowL {}
+impl GapXY for SidebarL {}
+impl GapXY for SwitcherL {}
+
+/// components having the `max-width` class
+pub trait MaxWidth {}
+impl MaxWidth for BoxL {}
+impl MaxWidth for CenterL {}
+
+/// components having the `recursive` class
+pub trait Recursive {}
+impl Recursive for CenterL {}
+impl Recursive for StackL {}
+
+/// components having the `reverse` class
+pub trait Reverse {}
+impl Reverse for SidebarL {}
+impl Reverse for SwitcherL {}
+
+/// components having the `align` class
+pub trait Align {}
+impl Align for IconL {}
+impl Align for RowL {}
+
+/// components having the `height` class
+pub trait Height {}
+impl Height for RackL {}
+impl Height for SliderL {}
+
+impl Layout {
+    pub fn area() -> Layout<AreaL> {
+        Layout::component("area-l")
+    }
+
+    pub fn r#box() -> Layout<BoxL> {
+        Layout::component("box-l")
+    }
+
+    pub fn center() -> Layout<CenterL> {
+        Layout::component("center-l")
+    }
+
+    pub fn extender() -> Layout<ExtenderL> {
+        Layout::component("extender-l")
+    }
+
+    pub fn grid() -> Layout<GridL> {
+        Layout::component("grid-l")
+    }
+
+    pub fn icon() -> Layout<IconL> {
+        Layout::component("icon-l")
+    }
+
+    pub fn row() -> Layout<RowL> {
+        Layout::component("row-l")
+    }
+
+    pub fn outsider() -> Layout<OutsiderL> {
+        Layout::component("outsider-l")
+    }
+
+    pub fn rack() -> Layout<RackL> {
+        Layout::component("rack-l")
+    }
+
+    pub fn sidebar() -> Layout<SidebarL> {
+        Layout::component("sidebar-l")
+    }
+
+    pub fn slider() -> Layout<SliderL> {
+        Layout::component("slider-l")
+    }
+
+    pub fn stack() -> Layout<StackL> {
+        Layout::component("stack-l")
+    }
+
+    pub fn switcher() -> Layout<SwitcherL> {
+        Layout::component("switcher-l")
+    }
+
+    /// an element which is not a component, for the utilities
+    pub fn utilities() -> Self {
+        Self::default()
+    }
+}
+
+impl<C: Gap> Layout<C> {
+    pub fn gap(self, value: &str) -> Self {
+        self.class(format!("gap:{value}"))
+    }
+}
+
+impl<C: GapXY> Layout<C> {
+    pub fn gap_x(self, value: &str) -> Self {
+        self.class(format!("gap-x:{value}"))
+    }
+
+    pub fn gap_y(self, value: &str) -> Self {
+        self.class(format!("gap-y:{value}"))
+    }
+}
+
+impl<C: MaxWidth> Layout<C> {
+    pub fn max_width(self, value: &str) -> Self {
+        self.class(format!("max-width:{value}"))
+    }
+}
+
+impl<C: Recursive> Layout<C> {
+    pub fn recursive(self) -> Self {
+        self.class("recursive".to_string())
+    }
+}
+
+impl<C: Reverse> Layout<C> {
+    pub fn reverse(self) -> Self {
+        self.class("reverse".to_string())
+    }
+}
+
+impl<C: Align> Layout<C> {
+    pub fn align(self, value: &str) -> Self {
+        self.class(format!("align:{value}"))
+    }
+}
+
+impl<C: Height> Layout<C> {
+    pub fn height(self, value: &str) -> Self {
+        self.class(format!("height:{value}"))
+    }
+}
+
+impl Layout<AreaL> {
+    /// `row-{index}:{value}` of area-l
+    pub fn area_row(self, index: usize, value: &str) -> Self {
+        self.class(format!("row-{index}:{value}"))
+    }
+
+    /// `col-{index}:{value}` of area-l
+    pub fn area_col(self, index: usize, value: &str) -> Self {
+        self.class(format!("col-{index}:{value}"))
+    }
+
+    pub fn template(self, value: &str) -> Self {
+        self.class(format!("template:{value}"))
+    }
+}
+
+impl Layout<BoxL> {
+    pub fn grow(self) -> Self {
+        self.class("grow".to_string())
+    }
+}
+
+impl Layout<CenterL> {
+    pub fn and_text(self) -> Self {
+        self.class("and-text".to_string())
+    }
+}
+
+impl Layout<ExtenderL> {
+    pub fn screen(self) -> Self {
+        self.class("screen".to_string())
+    }
+
+    pub fn keep_p(self) -> Self {
+        self.class("keep-p".to_string())
+    }
+
+    pub fn keep_pl(self) -> Self {
+        self.class("keep-pl".to_string())
+    }
+
+    pub fn keep_pr(self) -> Self {
+        self.class("keep-pr".to_string())
+    }
+
+    pub fn keep_center(self) -> Self {
+        self.class("keep-center".to_string())
+    }
+}
+
+impl Layout<GridL> {
+    pub fn min_cell_width(self, value: &str) -> Self {
+        self.class(format!("min-cell-width:{value}"))
+    }
+
+    pub fn min_cols(self, value: &str) -> Self {
+        self.class(format!("min-cols:{value}"))
+    }
+
+    pub fn max_cols(self, value: &str) -> Self {
+        self.class(format!("max-cols:{value}"))
+    }
+}
+
+impl Layout<IconL> {
+    pub fn gap_dir(self, value: &str) -> Self {
+        self.class(format!("gap-dir:{value}"))
+    }
+
+    pub fn scale(self, value: &str) -> Self {
+        self.class(format!("scale:{value}"))
+    }
+}
+
+impl Layout<RowL> {
+    pub fn direction(self, value: &str) -> Self {
+        self.class(format!("direction:{value}"))
+    }
+
+    pub fn justify(self, value: &str) -> Self {
+        self.class(format!("justify:{value}"))
+    }
+
+    pub fn twin_width(self) -> Self {
+        self.class("twin-width".to_string())
+    }
+
+    pub fn nowrap(self) -> Self {
+        self.class("nowrap".to_string())
+    }
+}
+
+impl Layout<OutsiderL> {
+    pub fn position(self, value: &str) -> Self {
+        self.class(format!("position:{value}"))
+    }
+
+    pub fn top(self, value: &str) -> Self {
+        self.class(format!("top:{value}"))
+    }
+
+    pub fn bottom(self, value: &str) -> Self {
+        self.class(format!("bottom:{value}"))
+    }
+
+    pub fn left(self, value: &str) -> Self {
+        self.class(format!("left:{value}"))
+    }
+
+    pub fn right(self, value: &str) -> Self {
+        self.class(format!("right:{value}"))
+    }
+
+    pub fn disinherit(self) -> Self {
+        self.class("disinherit".to_string())
+    }
+}
+
+impl Layout<RackL> {
+    pub fn min_height(self, value: &str) -> Self {
+        self.class(format!("min-height:{value}"))
+    }
+
+    pub fn max_height(self, value: &str) -> Self {
+        self.class(format!("max-height:{value}"))
+    }
+}
+
+impl Layout<SidebarL> {
+    pub fn side(self, value: &str) -> Self {
+        self.class(format!("side:{value}"))
+    }
+
+    pub fn side_width(self, value: &str) -> Self {
+        self.class(format!("side-width:{value}"))
+    }
+
+    pub fn content_min(self, value: &str) -> Self {
+        self.class(format!("content-min:{value}"))
+    }
+
+    pub fn shrink(self) -> Self {
+        self.class("shrink".to_string())
+    }
+}
+
+impl Layout<SliderL> {
+    pub fn item_width(self, value: &str) -> Self {
+        self.class(format!("item-width:{value}"))
+    }
+
+    pub fn hide_bar(self) -> Self {
+        self.class("hide-bar".to_string())
+    }
+}
+
+impl Layout<SwitcherL> {
+    pub fn threshold(self, value: &str) -> Self {
+        self.class(format!("threshold:{value}"))
+    }
+
+    pub fn limit(self, value: &str) -> Self {
+        self.class(format!("limit:{value}"))
+    }
+}
+
+impl<C: Default> Layout<C> {
+    fn component(tag: &'static str) -> Self {
+        Layout {
+            component: Some(tag),
+            ..Self::default()
+        }
+    }
+
+    /// Add the classes set by `breakpoint` to the `layoutNNNpx` attribute,
+    /// the breakpoints of the layout given to `breakpoint` are ignored.
+    /// In class mode the breakpoints are not written, `diagnostics` reports them.
+    pub fn at(mut self, width: usize, breakpoint: impl FnOnce(Self) -> Self) -> Self {
+        let Layout {
+            classes, rejected, ..
+        } = breakpoint(Layout {
+            component: self.component,
+            ..Self::default()
+        });
+        self.rejected.extend(rejected);
+        match self.breakpoints.iter_mut().find(|(w, _)| *w == width) {
+            Some((_, existing)) => existing.extend(classes),
+            None => self.breakpoints.push((width, classes)),
+        }
+        self
+    }
+}
+
+impl<C> Layout<C> {
+    /// add the class, or leave it out if it can't be written in an attribute
+    fn class(mut self, class: String) -> Self {
+        match class.contains(|c: char| c.is_whitespace() || c == '\'') {
+            true => self.rejected.push(class),
+            false => self.classes.push(class),
+        }
+        self
+    }
+
+    pub fn align_self(self, value: &str) -> Self {
+        self.class(format!("align-self:{value}"))
+    }
+
+    pub fn bg_img(self, value: &str) -> Self {
+        self.class(format!("bg-img:{value}"))
+    }
+
+    pub fn flex_basis(self, value: &str) -> Self {
+        self.class(format!("flex-basis:{value}"))
+    }
+
+    pub fn flex_grow(self, value: &str) -> Self {
+        self.class(format!("flex-grow:{value}"))
+    }
+
+    pub fn flex_shrink(self, value: &str) -> Self {
+        self.class(format!("flex-shrink:{value}"))
+    }
+
+    pub fn font_size(self, value: &str) -> Self {
+        self.class(format!("font-size:{value}"))
+    }
+
+    pub fn h(self, value: &str) -> Self {
+        self.class(format!("h:{value}"))
+    }
+
+    pub fn hide_over(self, value: &str) -> Self {
+        self.class(format!("hide-over:{value}"))
+    }
+
+    pub fn hide_under(self, value: &str) -> Self {
+        self.class(format!("hide-under:{value}"))
+    }
+
+    pub fn line_height(self, value: &str) -> Self {
+        self.class(format!("line-height:{value}"))
+    }
+
+    pub fn p(self, value: &str) -> Self {
+        self.class(format!("p:{value}"))
+    }
+
+    pub fn pt(self, value: &str) -> Self {
+        self.class(format!("pt:{value}"))
+    }
+
+    pub fn pb(self, value: &str) -> Self {
+        self.class(format!("pb:{value}"))
+    }
+
+    pub fn pl(self, value: &str) -> Self {
+        self.class(format!("pl:{value}"))
+    }
+
+    pub fn pr(self, value: &str) -> Self {
+        self.class(format!("pr:{value}"))
+    }
+
+    pub fn px(self, value: &str) -> Self {
+        self.class(format!("px:{value}"))
+    }
+
+    pub fn py(self, value: &str) -> Self {
+        self.class(format!("py:{value}"))
+    }
+
+    pub fn p_child(self, value: &str) -> Self {
+        self.class(format!("p-child:{value}"))
+    }
+
+    pub fn pt_child(self, value: &str) -> Self {
+        self.class(format!("pt-child:{value}"))
+    }
+
+    pub fn pb_child(self, value: &str) -> Self {
+        self.class(format!("pb-child:{value}"))
+    }
+
+    pub fn pl_child(self, value: &str) -> Self {
+        self.class(format!("pl-child:{value}"))
+    }
+
+    pub fn pr_child(self, value: &str) -> Self {
+        self.class(format!("pr-child:{value}"))
+    }
+
+    pub fn px_child(self, value: &str) -> Self {
+        self.class(format!("px-child:{value}"))
+    }
+
+    pub fn py_child(self, value: &str) -> Self {
+        self.class(format!("py-child:{value}"))
+    }
+
+    pub fn p_recursive(self, value: &str) -> Self {
+        self.class(format!("p-recursive:{value}"))
+    }
+
+    pub fn pt_recursive(self, value: &str) -> Self {
+        self.class(format!("pt-recursive:{value}"))
+    }
+
+    pub fn pb_recursive(self, value: &str) -> Self {
+        self.class(format!("pb-recursive:{value}"))
+    }
+
+    pub fn pl_recursive(self, value: &str) -> Self {
+        self.class(format!("pl-recursive:{value}"))
+    }
+
+    pub fn pr_recursive(self, value: &str) -> Self {
+        self.class(format!("pr-recursive:{value}"))
+    }
+
+    pub fn px_recursive(self, value: &str) -> Self {
+        self.class(format!("px-recursive:{value}"))
+    }
+
+    pub fn py_recursive(self, value: &str) -> Self {
+        self.class(format!("py-recursive:{value}"))
+    }
+
+    pub fn ratio(self, value: &str) -> Self {
+        self.class(format!("ratio:{value}"))
+    }
+
+    pub fn w(self, value: &str) -> Self {
+        self.class(format!("w:{value}"))
+    }
+
+    pub fn z_index(self, value: &str) -> Self {
+        self.class(format!("z-index:{value}"))
+    }
+
+    pub fn relative(self) -> Self {
+        self.class("relative".to_string())
+    }
+
+    pub fn container(self) -> Self {
+        self.class("container".to_string())
+    }
+
+    pub fn centered(self) -> Self {
+        self.class("centered".to_string())
+    }
+
+    /// return the tag name of the element in the naming of the config
+    pub fn tag_name(&self, layout_style_config: &LayoutStyleConfig) -> String {
+        match self.component {
+            Some(tag) if !layout_style_config.class_mode => element_name(tag, layout_style_config),
+            _ => "div".to_string(),
+        }
+    }
+
+    /// Return the attributes to write on the element, with their value.
+    /// In class mode, it's only the `class` attribute, because breakpoints can't be used.
+    pub fn attributes(&self, layout_style_config: &LayoutStyleConfig) -> Vec<(String, String)> {
+        if layout_style_config.class_mode {
+            let classes: Vec<String> = self
+                .component
+                .map(|tag| element_name(tag, layout_style_config))
+                .into_iter()
+                .chain(self.classes.iter().cloned())
+                .collect();
+            return vec![("class".to_string(), classes.join(" "))];
+        }
+        let attribute_name = &layout_style_config.attribute_name;
+        let mut attributes = vec![];
+        if !self.classes.is_empty() {
+            attributes.push((attribute_name.clone(), self.classes.join(" ")));
+        }
+        attributes.extend(self.breakpoint_attributes(attribute_name));
+        attributes
+    }
+
+    /// return the `layoutNNNpx` attributes of the breakpoints, with their value
+    fn breakpoint_attributes(&self, attribute_name: &str) -> Vec<(String, String)> {
+        self.breakpoints
+            .iter()
+            .map(|(width, classes)| (format!("{attribute_name}{width}px"), classes.join(" ")))
+            .collect()
+    }
+
+    /// return the attributes as written in html, like `layout="gap:2" layout600px="gap:1"`
+    pub fn attribute_string(&self, layout_style_config: &LayoutStyleConfig) -> String {
+        attribute_string(&self.attributes(layout_style_config))
+    }
+
+    /// return the opening tag of the element, like `<row-l layout="gap:2">`
+    pub fn opening_tag(&self, layout_style_config: &LayoutStyleConfig) -> String {
+        let tag_name = self.tag_name(layout_style_config);
+        match self.attribute_string(layout_style_config).as_str() {
+            "" => format!("<{tag_name}>"),
+            attributes => format!("<{tag_name} {attributes}>"),
+        }
+    }
+
+    /// Return the opening tag to parse for the diagnostics: in class mode,
+    /// the breakpoint attributes which are not written are kept in it,
+    /// so the parser reports them as ignored.
+    fn checked_tag(&self, layout_style_config: &LayoutStyleConfig) -> String {
+        if !layout_style_config.class_mode || self.breakpoints.is_empty() {
+            return self.opening_tag(layout_style_config);
+        }
+        let mut attributes = self.attributes(layout_style_config);
+        attributes.extend(self.breakpoint_attributes(&layout_style_config.attribute_name));
+        format!(
+            "<{} {}>",
+            self.tag_name(layout_style_config),
+            attribute_string(&attributes)
+        )
+    }
+
+    /// return the problems of the element, like a value which is not a css length
+    pub fn diagnostics(&self, layout_style_config: &LayoutStyleConfig) -> Vec<Diagnostic> {
+        let checked_tag = self.checked_tag(layout_style_config);
+        let mut parser = Parser::with_config(&checked_tag, layout_style_config);
+        parser.parse(&mut HashSet::new());
+        let mut diagnostics = self.rejected_diagnostics(layout_style_config);
+        diagnostics.extend(parser.diagnostics);
+        diagnostics
+    }
+
+    /// return the diagnostics of the classes left out of the attributes
+    fn rejected_diagnostics(&self, layout_style_config: &LayoutStyleConfig) -> Vec<Diagnostic> {
+        let tag_name = self.tag_name(layout_style_config);
+        self.rejected
+            .iter()
+            .map(|class| Diagnostic::new(DiagnosticKind::UnwritableValue, &tag_name, class))
+            .collect()
+    }
+
+    /// return the layout elements of the element, found by parsing its opening tag
+    pub fn elements(
+        &self,
+        layout_style_config: &LayoutStyleConfig,
+    ) -> HashSet<LayoutElement<'static>> {
+        let opening_tag = self.opening_tag(layout_style_config);
+        let mut elements = HashSet::new();
+        Parser::with_config(&opening_tag, layout_style_config).parse(&mut elements);
+        elements
+            .into_iter()
+            .map(LayoutElement::into_owned)
+            .collect()
+    }
+
+    /// Insert the css rules of the element (with the reset) into the sets passed,
+    /// like insert_css_rules does for a text, and return the problems found.
+    pub fn insert_css_rules(
+        &self,
+        css_rules: &mut HashSet<String>,
+        css_mq_rules: &mut HashMap<MediaQuery, HashSet<String>>,
+        layout_style_config: &LayoutStyleConfig,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = self.rejected_diagnostics(layout_style_config);
+        diagnostics.extend(insert_css_rules(
+            &self.checked_tag(layout_style_config),
+            css_rules,
+            css_mq_rules,
+            layout_style_config,
+        ));
+        diagnostics
+    }
+
+    /// return the stylesheet of this element alone
+    pub fn css(&self, layout_style_config: &LayoutStyleConfig) -> String {
+        let (mut css_rules, mut css_mq_rules) = (HashSet::new(), HashMap::new());
+        self.insert_css_rules(&mut css_rules, &mut css_mq_rules, layout_style_config);
+        generate_final_css_with_config(&css_rules, &css_mq_rules, layout_style_config)
+    }
+}
+
+/// return the attributes as written in html, separated by spaces
+fn attribute_string(attributes: &[(String, String)]) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| match value.contains('"') {
+            true => format!("{name}='{value}'"),
+            false => format!("{name}=\"{value}\""),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// return the name of the component tag in the naming of the config
+fn element_name(tag: &str, layout_style_config: &LayoutStyleConfig) -> String {
+    let naming = ElementNaming::new(&layout_style_config.element_name).unwrap_or_default();
+    naming.element_name(tag.strip_suffix("-l").unwrap_or(tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classes::LayoutClass;
+    use crate::components::Component;
+
+    #[test]
+    fn attributes_follow_the_builder() {
+        let config = LayoutStyleConfig::default();
+        let row = Layout::row()
+            .gap("2")
+            .justify("center")
+            .at(600, |row| row.direction("column"));
+        assert_eq!(
+            row.opening_tag(&config),
+            "<row-l layout=\"gap:2 justify:center\" layout600px=\"direction:column\">"
+        );
+        assert_eq!(
+            Layout::utilities()
+                .bg_img("url(\"a.png\")")
+                .attribute_string(&config),
+            "layout='bg-img:url(\"a.png\")'"
+        );
+    }
+
+    #[test]
+    fn elements_are_the_ones_of_the_markup() {
+        let config = LayoutStyleConfig::default();
+        let elements = Layout::row()
+            .gap("2")
+            .p("1")
+            .at(600, |row| row.direction("column"))
+            .elements(&config);
+        assert!(elements.iter().any(|element| matches!(
+            element,
+            LayoutElement::LayoutComponent(Component::Row { direction: Some(direction), .. }, Some(MediaQuery::InferiorOrEqualTo(600)))
+                if direction == "column"
+        )));
+        assert!(elements.contains(&LayoutElement::LayoutUtility(
+            LayoutClass::P("1".into()),
+            None
+        )));
+        assert!(Layout::row()
+            .at(600, |row| row.direction("column"))
+            .css(&config)
+            .contains("row-l[layout600px~=\"direction:column\"]"));
+    }
+
+    #[test]
+    fn class_mode_puts_everything_in_the_class_attribute() {
+        let config = LayoutStyleConfig {
+            class_mode: true,
+            element_name: "lc-{name}".to_string(),
+            ..LayoutStyleConfig::default()
+        };
+        assert_eq!(
+            Layout::stack().gap("1").opening_tag(&config),
+            "<div class=\"lc-stack gap:1\">"
+        );
+    }
+
+    #[test]
+    fn unwritable_values_and_dropped_breakpoints_are_reported() {
+        let config = LayoutStyleConfig::default();
+        let row = Layout::row().gap("1 p:9").bg_img("url('a\".png')").p("1");
+        assert_eq!(row.opening_tag(&config), "<row-l layout=\"p:1\">");
+        let diagnostics = row.diagnostics(&config);
+        let tokens: Vec<(&DiagnosticKind, &str)> = diagnostics
+            .iter()
+            .map(|d| (&d.kind, d.token.as_str()))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (&DiagnosticKind::UnwritableValue, "gap:1 p:9"),
+                (&DiagnosticKind::UnwritableValue, "bg-img:url('a\".png')"),
+            ]
+        );
+        assert_eq!(
+            Layout::stack().gap("2x").diagnostics(&config)[0].kind,
+            DiagnosticKind::InvalidValue
+        );
+        let config = LayoutStyleConfig {
+            class_mode: true,
+            ..LayoutStyleConfig::default()
+        };
+        let row = Layout::row().gap("2").at(600, |row| row.gap("1").justify("a b"));
+        assert_eq!(row.opening_tag(&config), "<div class=\"row-l gap:2\">");
+        let diagnostics = row.diagnostics(&config);
+        let tokens: Vec<(&DiagnosticKind, &str)> = diagnostics
+            .iter()
+            .map(|d| (&d.kind, d.token.as_str()))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (&DiagnosticKind::UnwritableValue, "justify:a b"),
+                (&DiagnosticKind::IgnoredInClassMode, "layout600px"),
+            ]
+        );
+        let (mut css_rules, mut css_mq_rules) = (HashSet::new(), HashMap::new());
+        assert_eq!(
+            row.insert_css_rules(&mut css_rules, &mut css_mq_rules, &config),
+            diagnostics
+        );
+        assert!(css_mq_rules.is_empty());
+    }
+}
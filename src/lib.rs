@@ -1,26 +1,115 @@
+pub mod build;
 pub mod builder;
 pub mod dev;
+pub mod files;
 pub mod classes;
+pub mod class_mode;
 pub mod reset;
+pub mod source_map;
+pub mod specificity;
+pub mod span;
+pub mod compiler;
 pub mod components;
 pub mod config;
+pub mod diagnostic;
+pub mod expression;
 pub mod harmonic;
+pub mod layout;
+pub mod macro_usage;
+pub mod manifest;
 pub mod media_query;
+pub mod minify;
+pub mod naming;
+pub mod ordering;
 pub mod parser;
 pub mod utilities;
 
 use builder::LayoutElement;
 use config::LayoutStyleConfig;
-use indoc::formatdoc;
+use diagnostic::Diagnostic;
 use media_query::MediaQuery;
+use source_map::{RuleOrigins, RulePosition};
 use std::collections::{HashMap, HashSet};
 
+/// Assemble the rules into the stylesheet, with the default output options.
 pub fn generate_final_css(
     css_set: &HashSet<String>,
     mq_rules: &HashMap<MediaQuery, HashSet<String>>,
 ) -> String {
-    // We join all the classic rules (non mq) into a single String
-    let mut final_css = css_set.iter().cloned().collect::<Vec<String>>().join("");
+    generate_final_css_with_config(css_set, mq_rules, &LayoutStyleConfig::default())
+}
+
+/// Assemble the rules into the stylesheet, the output options
+/// (like `minify`) are taken from the config.
+pub fn generate_final_css_with_config(
+    css_set: &HashSet<String>,
+    mq_rules: &HashMap<MediaQuery, HashSet<String>>,
+    layout_style_config: &LayoutStyleConfig,
+) -> String {
+    let (final_css, _) = assemble_css(css_set, mq_rules, layout_style_config);
+    let final_css = apply_output_options(final_css, layout_style_config);
+    if layout_style_config.minify {
+        return minify::minify_css(&final_css);
+    }
+    final_css
+}
+
+/// Same as generate_final_css_with_config, but also returns the source map (version 3)
+/// linking every rule to the first markup location producing it, found in `origins`.
+/// `file` is the name of the stylesheet written in the map.
+/// The map points into the lines of the stylesheet, so it's never minified.
+pub fn generate_final_css_with_source_map(
+    css_set: &HashSet<String>,
+    mq_rules: &HashMap<MediaQuery, HashSet<String>>,
+    layout_style_config: &LayoutStyleConfig,
+    origins: &RuleOrigins,
+    file: &str,
+) -> (String, String) {
+    let (final_css, positions) = assemble_css(css_set, mq_rules, layout_style_config);
+    // the other output options keep the lines and the start of the rules,
+    // so the positions found in the assembled css are still right after them
+    let source_map = origins.source_map(file, &final_css, &positions);
+    (
+        apply_output_options(final_css, layout_style_config),
+        source_map,
+    )
+}
+
+/// join the rules, sorted and scoped to their breakpoints, and return where each rule starts
+fn assemble_css(
+    css_set: &HashSet<String>,
+    mq_rules: &HashMap<MediaQuery, HashSet<String>>,
+    layout_style_config: &LayoutStyleConfig,
+) -> (String, Vec<RulePosition>) {
+    let names = naming::SelectorNames::from_config(layout_style_config);
+    let mut final_css = String::new();
+    let mut positions = vec![];
+    if layout_style_config.layers {
+        final_css.push_str(&format!("@layer {};\n", ordering::LAYERS.join(", ")));
+    }
+    // We join all the classic rules (non mq) into a single String,
+    // sorted so the stylesheet is always the same for the same rules,
+    // the rules are sorted by group, so the rules of a layer follow each other
+    let mut current_layer = None;
+    for rule in sorted_rules(css_set, layout_style_config, &names) {
+        if layout_style_config.layers {
+            let layer = ordering::rule_group(rule, &names.element_naming).layer();
+            if current_layer != Some(layer) {
+                if current_layer.is_some() {
+                    final_css.push_str(LAYER_END);
+                }
+                final_css.push_str(&layer_start(layer));
+                current_layer = Some(layer);
+            }
+        }
+        push_rule(&mut final_css, &mut positions, rule, rule, None);
+    }
+    if current_layer.is_some() {
+        final_css.push_str(LAYER_END);
+    }
+
+    let mut breakpoints_css = String::new();
+    let mut breakpoints_positions = vec![];
 
     // Now we have to process the media queries,
     // we get the keys in the correct order to process them to get a
@@ -32,46 +121,187 @@ pub fn generate_final_css(
 
     for key in ordered_mq_keys {
         if let Some(hash_set) = mq_rules.get(&key) {
-            // replace selector in media query to increase specificity
-            // to avoid conflict
-            let rule_with_modified_selector: String = hash_set
-                .iter()
-                .map(|s| match &key {
-                    MediaQuery::InferiorOrEqualTo(breakpoint) => {
-                        s.replace("[layout", format!("[layout{breakpoint}px").as_str())
-                    }
-                    MediaQuery::SuperiorTo(breakpoint, attribute_value) => s.replace(
-                        "-l[layout",
-                        format!("-l[layout{breakpoint}px=\"{attribute_value}\"][layout")
-                            .as_str(),
-                    ),
-                })
-                .collect::<Vec<String>>()
-                .join("");
-            // wrap the previous modified rule
-            // into a media query
-            let wrapped_in_mq = match key {
-                MediaQuery::SuperiorTo(breakpoint, _) => formatdoc!(
-                    r#"
-                            @media (width > {breakpoint}px) {{
-                                {rule_with_modified_selector}
-                            }}
-                            "#
-                ),
-                MediaQuery::InferiorOrEqualTo(breakpoint) => formatdoc!(
-                    r#"
-                            @media (width <= {breakpoint}px) {{
-                                {rule_with_modified_selector}
-                            }}"#
-                ),
+            // the rules are wrapped into a media query
+            let (at_rule, end) = match key {
+                MediaQuery::SuperiorTo(breakpoint, _) => {
+                    (format!("@media (width > {breakpoint}px)"), "}\n")
+                }
+                MediaQuery::InferiorOrEqualTo(breakpoint) => {
+                    (format!("@media (width <= {breakpoint}px)"), "}")
+                }
+                MediaQuery::InferiorTo(breakpoint, _) => {
+                    (format!("@media (width < {breakpoint}px)"), "}\n")
+                }
+                MediaQuery::SuperiorOrEqualTo(breakpoint) => {
+                    (format!("@media (width >= {breakpoint}px)"), "}\n")
+                }
+                MediaQuery::ContainerInferiorOrEqualTo(breakpoint) => {
+                    (format!("@container (width <= {breakpoint}px)"), "}\n")
+                }
+                MediaQuery::ContainerSuperiorOrEqualTo(breakpoint) => {
+                    (format!("@container (width >= {breakpoint}px)"), "}\n")
+                }
             };
-            // at this rules to the final css
-            final_css.push_str(wrapped_in_mq.as_str());
+            breakpoints_css.push_str(&format!("{at_rule} {{\n    "));
+            // a breakpoint can be set by `layout600px` or by the name
+            // given to it in the config, like `layout-md`
+            let attributes = media_query::breakpoint_attributes(
+                &key.attribute_prefix(&names.attribute),
+                *key.get_breakpoint(),
+                &layout_style_config.breakpoints,
+            );
+            for s in sorted_rules(hash_set, layout_style_config, &names) {
+                // replace selector in media query to increase specificity
+                // to avoid conflict
+                let rule_with_modified_selector = match &key {
+                    MediaQuery::SuperiorTo(_, attribute_value)
+                    | MediaQuery::InferiorTo(_, attribute_value) => {
+                        // an empty value is only known at runtime, any value is selected
+                        let breakpoint_selector = any_of(
+                            attributes
+                                .iter()
+                                .map(|attribute| match attribute_value.as_str() {
+                                    "" => format!("[{attribute}]"),
+                                    value => format!("[{attribute}=\"{value}\"]"),
+                                })
+                                .collect(),
+                        );
+                        scope_components_to_breakpoint(s, &names, &breakpoint_selector)
+                    }
+                    _ => scope_to_breakpoint_attributes(s, &names.attribute, &attributes),
+                };
+                push_rule(
+                    &mut breakpoints_css,
+                    &mut breakpoints_positions,
+                    &rule_with_modified_selector,
+                    s,
+                    Some(key.clone()),
+                );
+            }
+            breakpoints_css.push('\n');
+            breakpoints_css.push_str(end);
         }
     }
+    if layout_style_config.layers && !breakpoints_css.is_empty() {
+        final_css.push_str(&layer_start(ordering::LAYERS[3]));
+    }
+    for mut position in breakpoints_positions {
+        position.offset += final_css.len();
+        positions.push(position);
+    }
+    final_css.push_str(&breakpoints_css);
+    if layout_style_config.layers && !breakpoints_css.is_empty() {
+        final_css.push_str(LAYER_END);
+    }
+    (final_css, positions)
+}
+
+/// return the rules in the order they are written in the stylesheet
+fn sorted_rules<'a>(
+    rules: &'a HashSet<String>,
+    layout_style_config: &LayoutStyleConfig,
+    names: &naming::SelectorNames,
+) -> Vec<&'a String> {
+    // the rules lose their specificity, the more specific ones must come last
+    match layout_style_config.low_specificity {
+        true => ordering::sorted_rules_by_specificity(rules, &names.element_naming),
+        false => ordering::sorted_rules(rules, &names.element_naming),
+    }
+}
+
+/// add the rule to the css, and where it starts to the positions
+fn push_rule(
+    css: &mut String,
+    positions: &mut Vec<RulePosition>,
+    written_rule: &str,
+    generated_rule: &str,
+    media_query: Option<MediaQuery>,
+) {
+    positions.push(RulePosition {
+        offset: css.len() + written_rule.len() - written_rule.trim_start().len(),
+        media_query,
+        rule: generated_rule.to_string(),
+    });
+    css.push_str(written_rule);
+}
+
+/// rewrite the assembled css for the output options of the config, except minify
+fn apply_output_options(mut final_css: String, layout_style_config: &LayoutStyleConfig) -> String {
+    // in class mode, the components and their classes are selected as classes
+    if layout_style_config.class_mode {
+        let names = naming::SelectorNames::from_config(layout_style_config);
+        final_css = class_mode::to_class_selectors(&final_css, &names);
+    }
+    if layout_style_config.low_specificity {
+        final_css = specificity::remove_specificity(&final_css);
+    }
     final_css
 }
 
+/// end of the block of a cascade layer
+const LAYER_END: &str = "\n}\n";
+
+/// return the start of the block of the cascade layer named `layer`
+fn layer_start(layer: &str) -> String {
+    format!("@layer {layer} {{\n")
+}
+
+/// return a selector matching one of the selectors
+fn any_of(selectors: Vec<String>) -> String {
+    match selectors.len() {
+        1 => selectors.concat(),
+        _ => format!(":is({})", selectors.join(",")),
+    }
+}
+
+/// add the breakpoint selector to the components selected with a `[layout...]` selector,
+/// `row-l[layout~="gap:2"]` becomes `row-l[layout600px][layout~="gap:2"]`
+fn scope_components_to_breakpoint(
+    rule: &str,
+    names: &naming::SelectorNames,
+    breakpoint_selector: &str,
+) -> String {
+    let attribute = &names.attribute;
+    let mut scoped = rule.to_string();
+    for component_tag in components::COMPONENT_TAGS {
+        let tag = names.tag(component_tag.trim_end_matches("-l"));
+        scoped = scoped.replace(
+            &format!("{tag}[{attribute}"),
+            &format!("{tag}{breakpoint_selector}[{attribute}"),
+        );
+    }
+    scoped
+}
+
+/// replace the `[layout...]` selectors of the rule, `layout` being the `attribute`,
+/// by the same selectors on the breakpoint attributes,
+/// `[layout~="p:2"]` becomes `[layout600px~="p:2"]`
+fn scope_to_breakpoint_attributes(rule: &str, attribute: &str, attributes: &[String]) -> String {
+    let selector_start = format!("[{attribute}");
+    let mut scoped = String::with_capacity(rule.len());
+    let mut rest = rule;
+    while let Some(start) = rest.find(&selector_start) {
+        scoped.push_str(&rest[..start]);
+        let after_name = &rest[start + selector_start.len()..];
+        // another attribute starting with the same name, like `[layoutc`
+        if !after_name.starts_with(['~', '*', '=', ']']) {
+            scoped.push_str(&selector_start);
+            rest = after_name;
+            continue;
+        }
+        let end = after_name.find(']').map_or(after_name.len(), |end| end + 1);
+        scoped.push_str(&any_of(
+            attributes
+                .iter()
+                .map(|attribute| format!("[{attribute}{}", &after_name[..end]))
+                .collect(),
+        ));
+        rest = &after_name[end..];
+    }
+    scoped.push_str(rest);
+    scoped
+}
+
 /// This function can take css_set and css_mq_rules if you already have
 /// process a string before and you want to avoid to get duplicate values
 /// this way the new css will contains the previous css plus new rules from the new string
@@ -81,28 +311,112 @@ pub fn get_css_from_string<'a>(
     previous_css_mq_rules: Option<&mut HashMap<MediaQuery, HashSet<String>>>,
     layout_style_config: &LayoutStyleConfig,
 ) -> String {
-    let mut layout_elements: HashSet<LayoutElement> = HashSet::new();
+    let (css, _) = get_css_and_diagnostics(
+        text,
+        previous_css_rules,
+        previous_css_mq_rules,
+        layout_style_config,
+    );
+    css
+}
 
+/// Same as get_css_from_string, but also returns the problems found in the text
+/// (unknown classes, classes without effect, malformed breakpoints...).
+pub fn get_css_and_diagnostics(
+    text: &str,
+    previous_css_rules: Option<&mut HashSet<String>>,
+    previous_css_mq_rules: Option<&mut HashMap<MediaQuery, HashSet<String>>>,
+    layout_style_config: &LayoutStyleConfig,
+) -> (String, Vec<Diagnostic>) {
     let mut local_css_rules: HashSet<String> = HashSet::new();
     let css_rules = match previous_css_rules {
         Some(x) => x,
         None => &mut local_css_rules,
     };
 
-    css_rules.insert(reset::reset_css(&layout_style_config));
-    if layout_style_config.dev {
-        css_rules.insert(dev::DEV_CSS.to_string());
-    }
-
     let mut local_css_mq_rules: HashMap<MediaQuery, HashSet<String>> = HashMap::new();
     let css_mq_rules = match previous_css_mq_rules {
         Some(x) => x,
         None => &mut local_css_mq_rules,
     };
-    let mut parser = parser::Parser::new(text);
+    let diagnostics = insert_css_rules(text, css_rules, css_mq_rules, layout_style_config);
+    (
+        generate_final_css_with_config(css_rules, css_mq_rules, layout_style_config),
+        diagnostics,
+    )
+}
+
+/// Parse the text and insert the css rules it needs (with the reset)
+/// into the sets passed, without generating the final css.
+/// It's useful to keep the rules of several texts separated and merge them later.
+pub fn insert_css_rules(
+    text: &str,
+    css_rules: &mut HashSet<String>,
+    css_mq_rules: &mut HashMap<MediaQuery, HashSet<String>>,
+    layout_style_config: &LayoutStyleConfig,
+) -> Vec<Diagnostic> {
+    parse_and_insert_css_rules(text, css_rules, css_mq_rules, layout_style_config).diagnostics
+}
+
+/// Same as insert_css_rules, but also records in `origins` where the rules
+/// come from in the text, which is the document `source` of the origins.
+pub fn insert_css_rules_with_origins(
+    text: &str,
+    css_rules: &mut HashSet<String>,
+    css_mq_rules: &mut HashMap<MediaQuery, HashSet<String>>,
+    layout_style_config: &LayoutStyleConfig,
+    origins: &mut RuleOrigins,
+    source: usize,
+) -> Vec<Diagnostic> {
+    let parser = parse_and_insert_css_rules(text, css_rules, css_mq_rules, layout_style_config);
+    origins.record(source, &parser.sources, layout_style_config);
+    parser.diagnostics
+}
+
+/// parse the text, insert the css rules it needs and return the parser
+fn parse_and_insert_css_rules<'a>(
+    text: &'a str,
+    css_rules: &mut HashSet<String>,
+    css_mq_rules: &mut HashMap<MediaQuery, HashSet<String>>,
+    layout_style_config: &LayoutStyleConfig,
+) -> parser::Parser<'a> {
+    let mut layout_elements: HashSet<LayoutElement> = HashSet::new();
+
+    let names = naming::SelectorNames::from_config(layout_style_config);
+    css_rules.insert(reset::reset_css(layout_style_config));
+    if layout_style_config.dev {
+        css_rules.insert(dev::dev_css(&names));
+    }
+
+    let mut parser = parser::Parser::with_config(text, layout_style_config);
     parser.parse(&mut layout_elements);
     for element in layout_elements.drain() {
-        element.insert_css(layout_style_config.harmonic_ratio, css_rules, css_mq_rules);
+        element.insert_css_with_names(
+            layout_style_config.harmonic_ratio,
+            &names,
+            css_rules,
+            css_mq_rules,
+        );
+    }
+    parser
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_select_the_configured_attribute() {
+        let config = LayoutStyleConfig::from_toml_str("attribute_name = \"data-l\"").unwrap();
+        let (css, diagnostics) = get_css_and_diagnostics(
+            "<row-l data-l=\"gap:2\" data-l600px=\"p:1\" data-lang=\"en\">",
+            None,
+            None,
+            &config,
+        );
+        assert!(diagnostics.is_empty());
+        assert!(css.contains("row-l[data-l600px=\"p:1\"][data-l~=\"gap:2\"]"));
+        assert!(css.contains("[data-l600px~=\"p:1\"]"));
+        assert!(!css.contains("[layout"));
     }
-    generate_final_css(&css_rules, &css_mq_rules)
 }
@@ -0,0 +1,343 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::components::COMPONENT_TAGS;
+use crate::config::{find_config_file, LayoutStyleConfig};
+use crate::diagnostic::Diagnostic;
+use crate::naming::ElementNaming;
+use crate::parser::Parser;
+
+/// Name of the macro of the layoutcss-macros crate, like `layout!("row-l", "gap:2")`.
+pub const MACRO_NAME: &str = "layout!";
+
+/// Return the opening tag of an element with the layout attribute `classes`,
+/// named as in the config. In class mode, the element is a div
+/// with the component and its classes in the class attribute.
+pub fn invocation_markup(tag: &str, classes: &str, layout_style_config: &LayoutStyleConfig) -> String {
+    let naming = ElementNaming::new(&layout_style_config.element_name).unwrap_or_default();
+    let (tag, attribute, value) = match layout_style_config.class_mode {
+        true if naming.component_tag(tag).is_some() => ("div", "class", format!("{tag} {classes}")),
+        true => (tag, "class", classes.to_string()),
+        false => (tag, layout_style_config.attribute_name.as_str(), classes.to_string()),
+    };
+    match value.contains('"') {
+        true => format!("<{tag} {attribute}='{value}'>"),
+        false => format!("<{tag} {attribute}=\"{value}\">"),
+    }
+}
+
+/// Return the config of the crate using the macro: the closest `layoutcss.toml`
+/// from the `manifest_dir` of the crate, or the default one without it.
+pub fn crate_config(manifest_dir: impl AsRef<Path>) -> Result<LayoutStyleConfig, String> {
+    match find_config_file(manifest_dir) {
+        Some(config_path) => LayoutStyleConfig::from_path(&config_path)
+            .map_err(|error| format!("{}: {error}", config_path.display())),
+        None => Ok(LayoutStyleConfig::default()),
+    }
+}
+
+/// return the path of the `layoutcss.toml` of the crate using the macro,
+/// the closest one from its `CARGO_MANIFEST_DIR`, read by validate_invocation
+pub fn crate_config_path() -> Option<PathBuf> {
+    find_config_file(std::env::var_os("CARGO_MANIFEST_DIR")?)
+}
+
+/// Return the problems of the classes on the tag, as the `layout!` macro checks them
+/// at compile time, with the config of the crate found from `CARGO_MANIFEST_DIR`.
+pub fn validate_invocation(tag: &str, classes: &str) -> Result<(), Vec<String>> {
+    let layout_style_config = match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) => crate_config(manifest_dir).map_err(|problem| vec![problem])?,
+        None => LayoutStyleConfig::default(),
+    };
+    validate_invocation_with_config(tag, classes, &layout_style_config)
+}
+
+/// Return the problems of the classes on the tag, with the config passed.
+pub fn validate_invocation_with_config(
+    tag: &str,
+    classes: &str,
+    layout_style_config: &LayoutStyleConfig,
+) -> Result<(), Vec<String>> {
+    let naming = ElementNaming::new(&layout_style_config.element_name).unwrap_or_default();
+    if naming.is_element_name(tag) && naming.component_tag(tag).is_none() {
+        let component_names: Vec<String> = COMPONENT_TAGS
+            .iter()
+            .map(|component_tag| {
+                naming.element_name(component_tag.strip_suffix("-l").unwrap_or(component_tag))
+            })
+            .collect();
+        return Err(vec![format!(
+            "unknown component <{tag}>, expected one of {}",
+            component_names.join(", ")
+        )]);
+    }
+    let markup = invocation_markup(tag, classes, layout_style_config);
+    let mut parser = Parser::with_config(&markup, layout_style_config);
+    parser.parse(&mut HashSet::new());
+    match parser.diagnostics.is_empty() {
+        true => Ok(()),
+        false => Err(parser
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                // the position in the generated markup means nothing for the user
+                Diagnostic {
+                    span: None,
+                    ..diagnostic.clone()
+                }
+                .to_string()
+            })
+            .collect()),
+    }
+}
+
+/// Return the markup of every `layout!("tag", "classes")` written in the rust source,
+/// one opening tag by line, so the css of the macro invocations can be generated
+/// like for a template, with the same config.
+pub fn invocations_markup(source: &str, layout_style_config: &LayoutStyleConfig) -> String {
+    let mut markup = String::new();
+    let mut rest = source;
+    while let Some(start) = rest.find(MACRO_NAME) {
+        rest = &rest[start + MACRO_NAME.len()..];
+        let Some(arguments) = rest.trim_start().strip_prefix(['(', '[', '{']) else {
+            continue;
+        };
+        let Some((tag, after_tag)) = string_literal(arguments.trim_start()) else {
+            continue;
+        };
+        let Some(after_comma) = after_tag.trim_start().strip_prefix(',') else {
+            continue;
+        };
+        if let Some((classes, _)) = string_literal(after_comma.trim_start()) {
+            markup.push_str(&invocation_markup(&tag, &classes, layout_style_config));
+            markup.push('\n');
+        }
+    }
+    markup
+}
+
+/// Return the value of every string literal of the rust source,
+/// where components often write their markup.
+/// The comments, the char literals and the lifetimes are skipped.
+pub fn string_literals(source: &str) -> Vec<String> {
+    let mut literals = vec![];
+    let mut rest = source;
+    while let Some(start) = rest.find(['"', '\'', '/']) {
+        let (before, text) = rest.split_at(start);
+        rest = if text.starts_with("//") {
+            text.find('\n').map_or("", |end| &text[end..])
+        } else if let Some(comment) = text.strip_prefix("/*") {
+            after_block_comment(comment)
+        } else if let Some(quoted) = text.strip_prefix('\'') {
+            after_char_literal(quoted)
+        } else if let Some(after_slash) = text.strip_prefix('/') {
+            after_slash
+        } else {
+            // a raw string starts with `r` and its hashes, like r#"a"#
+            let hashes = before.len() - before.trim_end_matches('#').len();
+            let literal_start = match before[..start - hashes].ends_with('r') {
+                true => start - hashes - 1,
+                false => start,
+            };
+            match string_literal(&rest[literal_start..]) {
+                Some((value, after)) => {
+                    literals.push(value);
+                    after
+                }
+                None => break,
+            }
+        };
+    }
+    literals
+}
+
+/// return the text after the block comment starting the text (after its `/*`),
+/// block comments can be nested in rust
+fn after_block_comment(text: &str) -> &str {
+    let mut depth = 1;
+    let mut i = 0;
+    while i < text.len() {
+        if text.as_bytes()[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if text.as_bytes()[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return &text[i..];
+            }
+        } else {
+            i += 1;
+        }
+    }
+    ""
+}
+
+/// Return the text after the char literal starting the text (after its `'`),
+/// like `"'` or `\''`. A lifetime or a label like `'a` is not a char literal,
+/// the text is returned as is.
+fn after_char_literal(text: &str) -> &str {
+    let mut chars = text.char_indices();
+    match chars.next() {
+        // an escape like '\'' or '\u{22}'
+        Some((_, '\\')) => {
+            let escape = chars.as_str();
+            // the escaped char can be the quote itself
+            match escape.char_indices().skip(1).find(|(_, c)| *c == '\'') {
+                Some((end, _)) => &escape[end + 1..],
+                None => "",
+            }
+        }
+        Some(_) => match chars.next() {
+            Some((end, '\'')) => &text[end + 1..],
+            _ => text,
+        },
+        None => text,
+    }
+}
+
+/// Read the string literal at the start of the text, raw or not,
+/// return its value and the text after it.
+fn string_literal(text: &str) -> Option<(String, &str)> {
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let content = raw[hashes..].strip_prefix('"')?;
+        let end_quote = format!("\"{}", "#".repeat(hashes));
+        let end = content.find(&end_quote)?;
+        return Some((
+            content[..end].to_string(),
+            &content[end + end_quote.len()..],
+        ));
+    }
+    let content = text.strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = content.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &content[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                // a line continuation skips the whitespace at the start of the next line
+                '\n' => while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {},
+                escaped => value.push(escaped),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invocations_are_validated() {
+        let config = LayoutStyleConfig::default();
+        assert_eq!(
+            validate_invocation_with_config("row-l", "gap:2 justify:center", &config),
+            Ok(())
+        );
+        assert_eq!(
+            validate_invocation_with_config("row-l", "gap:2 max-cols:3 justfy:center", &config),
+            Err(vec![
+                "layout class `max-cols:3` has no effect on <row-l>".to_string(),
+                "unknown layout class `justfy:center` on <row-l>".to_string(),
+            ])
+        );
+        assert!(validate_invocation_with_config("rwo-l", "gap:2", &config).is_err());
+        assert_eq!(validate_invocation_with_config("div", "p:1", &config), Ok(()));
+    }
+
+    #[test]
+    fn invocations_are_validated_with_the_config() {
+        let config = LayoutStyleConfig {
+            attribute_name: "data-layout".to_string(),
+            element_name: "lc-{name}".to_string(),
+            ..LayoutStyleConfig::default()
+        };
+        assert_eq!(validate_invocation_with_config("lc-row", "gap:2", &config), Ok(()));
+        assert_eq!(
+            validate_invocation_with_config("lc-row", "max-cols:3", &config),
+            Err(vec!["layout class `max-cols:3` has no effect on <lc-row>".to_string()])
+        );
+        assert!(validate_invocation_with_config("lc-rwo", "gap:2", &config)
+            .unwrap_err()[0]
+            .ends_with("lc-stack, lc-switcher"));
+        let class_mode = LayoutStyleConfig {
+            class_mode: true,
+            ..config
+        };
+        assert_eq!(
+            invocation_markup("lc-row", "gap:2", &class_mode),
+            "<div class=\"lc-row gap:2\">"
+        );
+        assert!(validate_invocation_with_config("lc-row", "max-cols:3", &class_mode).is_err());
+    }
+
+    #[test]
+    fn the_config_of_the_crate_is_found_from_its_manifest_dir() {
+        let root = std::env::temp_dir().join(format!("layoutcss-macro-{}", std::process::id()));
+        let manifest_dir = root.join("app");
+        std::fs::create_dir_all(&manifest_dir).unwrap();
+        std::fs::write(root.join("layoutcss.toml"), "element_name = \"lc-{name}\"").unwrap();
+        let config = crate_config(&manifest_dir);
+        std::fs::write(root.join("layoutcss.toml"), "harmonic_ratio = -2.0").unwrap();
+        let invalid = crate_config(&manifest_dir);
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(config.unwrap().element_name, "lc-{name}");
+        assert!(invalid.unwrap_err().contains("harmonic_ratio"));
+    }
+
+    #[test]
+    fn invocations_are_found_in_rust_sources() {
+        let source = r###"
+            let row = layout!("row-l", "gap:2 justify:center");
+            let stack = layout! { "stack-l" , r#"gap:1 bg-img:url("a.png")"# };
+            let not_an_invocation = "layout!";
+        "###;
+        assert_eq!(
+            invocations_markup(source, &LayoutStyleConfig::default()),
+            "<row-l layout=\"gap:2 justify:center\">\n<stack-l layout='gap:1 bg-img:url(\"a.png\")'>\n"
+        );
+        let config = LayoutStyleConfig {
+            attribute_name: "data-layout".to_string(),
+            element_name: "lc-{name}".to_string(),
+            ..LayoutStyleConfig::default()
+        };
+        assert_eq!(
+            invocations_markup("layout!(\"lc-row\", \"gap:2\")", &config),
+            "<lc-row data-layout=\"gap:2\">\n"
+        );
+    }
+
+    #[test]
+    fn comments_chars_and_lifetimes_are_not_string_literals() {
+        let source = r###"
+            // a 5" screen
+            let a = "<row-l layout=\"gap:3\">";
+            /* a "quote" /* nested " */ */
+            fn b<'a>(s: &'a str) -> char { '\'' }
+            let c = ['"', '\"', '\u{22}'];
+            let d = "<box-l layout=\"p:1\">";
+        "###;
+        assert_eq!(
+            string_literals(source),
+            vec!["<row-l layout=\"gap:3\">", "<box-l layout=\"p:1\">"]
+        );
+    }
+
+    #[test]
+    fn string_literals_are_unescaped() {
+        let source = r###"
+            let quote = '"';
+            let a = "<row-l layout=\"gap:2\">";
+            let b = r#"<box-l layout="p:1">"#;
+        "###;
+        assert_eq!(
+            string_literals(source),
+            vec!["<row-l layout=\"gap:2\">", "<box-l layout=\"p:1\">"]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::LayoutStyleConfig;
+use crate::media_query::MediaQuery;
+use crate::naming::SelectorNames;
+use crate::parser::{ElementSource, Parser};
+use crate::{dev, reset};
+
+/// Every layout element found in a document, in the order of the text,
+/// with where it comes from.
+/// Once deserialized, it can be kept as `DocumentManifest<'static>`
+/// to generate the css of the document again without parsing it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DocumentManifest<'a> {
+    /// path of the document, like the path of the template
+    pub path: String,
+    pub elements: Vec<ElementSource<'a>>,
+}
+
+impl<'a> DocumentManifest<'a> {
+    /// parse the text of the document at `path` to find its layout elements
+    pub fn parse(path: &str, text: &'a str, layout_style_config: &LayoutStyleConfig) -> Self {
+        let mut parser = Parser::with_config(text, layout_style_config);
+        parser.parse(&mut HashSet::new());
+        DocumentManifest {
+            path: path.to_string(),
+            elements: parser.sources,
+        }
+    }
+
+    /// Insert the css rules of the document into the sets passed,
+    /// like insert_css_rules does with its text.
+    pub fn insert_css(
+        &self,
+        css_rules: &mut HashSet<String>,
+        css_mq_rules: &mut HashMap<MediaQuery, HashSet<String>>,
+        layout_style_config: &LayoutStyleConfig,
+    ) {
+        let names = SelectorNames::from_config(layout_style_config);
+        css_rules.insert(reset::reset_css(layout_style_config));
+        if layout_style_config.dev {
+            css_rules.insert(dev::dev_css(&names));
+        }
+        for source in &self.elements {
+            source.element.clone().insert_css_with_names(
+                layout_style_config.harmonic_ratio,
+                &names,
+                css_rules,
+                css_mq_rules,
+            );
+        }
+    }
+}
+
+/// Report of the components, classes and breakpoints used by documents,
+/// written in json to be read by other tools.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest<'a> {
+    pub documents: Vec<DocumentManifest<'a>>,
+}
+
+impl<'a> Manifest<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// return the manifest as indented json
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("the manifest only contains serializable values")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialized_documents_give_the_same_css() {
+        let config = LayoutStyleConfig::default();
+        let text = "<row-l layout=\"gap:2 p:1\" layout600px=\"gap:1\"><p layout=\"h:2\">";
+        let json =
+            serde_json::to_string(&DocumentManifest::parse("a.html", text, &config)).unwrap();
+        let document: DocumentManifest<'static> = serde_json::from_str(&json).unwrap();
+
+        let (mut rules, mut mq_rules) = (HashSet::new(), HashMap::new());
+        document.insert_css(&mut rules, &mut mq_rules, &config);
+        let (mut parsed_rules, mut parsed_mq_rules) = (HashSet::new(), HashMap::new());
+        crate::insert_css_rules(text, &mut parsed_rules, &mut parsed_mq_rules, &config);
+        assert_eq!(rules, parsed_rules);
+        assert_eq!(mq_rules, parsed_mq_rules);
+    }
+
+    #[test]
+    fn elements_are_written_with_their_location() {
+        let config = LayoutStyleConfig::default();
+        let mut manifest = Manifest::new();
+        manifest.documents.push(DocumentManifest::parse(
+            "index.html",
+            "<p>\n<row-l layout=\"gap:2 p:1\" layout600px=\"gap:1\">",
+            &config,
+        ));
+        let json: serde_json::Value = serde_json::from_str(&manifest.to_json()).unwrap();
+        let document = &json["documents"][0];
+        assert_eq!(document["path"], "index.html");
+        let elements = document["elements"].as_array().unwrap();
+        assert_eq!(elements.len(), 3);
+        let utility = elements
+            .iter()
+            .find(|element| element["element"].get("LayoutUtility").is_some())
+            .unwrap();
+        assert_eq!(
+            utility["element"]["LayoutUtility"],
+            serde_json::json!([{ "P": "1" }, null])
+        );
+        assert_eq!(utility["token"]["line"], 2);
+        assert_eq!(utility["token"]["column"], 22);
+        assert!(elements
+            .iter()
+            .any(|element| element["element"]["LayoutComponent"][1]
+                == serde_json::json!({ "InferiorOrEqualTo": 600 })));
+    }
+}
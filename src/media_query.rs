@@ -1,9 +1,25 @@
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+/// Desktop first (the default), `layout600px` applies up to 600px
+/// and `layout` beyond the biggest breakpoint of the tag:
+/// `InferiorOrEqualTo` and `SuperiorTo` are used.
+/// Mobile first, `layout600px` applies from 600px
+/// and `layout` below the smallest breakpoint of the tag:
+/// `SuperiorOrEqualTo` and `InferiorTo` are used.
+/// The value of `SuperiorTo` and `InferiorTo` is the one of the breakpoint attribute
+/// of the tag, empty when it's an expression only known at runtime.
+/// The `Container` variants come from `layoutc600px` attributes
+/// and are emitted as `@container` rules.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum MediaQuery {
     SuperiorTo(usize, String),
     InferiorOrEqualTo(usize),
+    InferiorTo(usize, String),
+    SuperiorOrEqualTo(usize),
+    ContainerInferiorOrEqualTo(usize),
+    ContainerSuperiorOrEqualTo(usize),
 }
 
 impl MediaQuery {
@@ -11,6 +27,39 @@ impl MediaQuery {
         match self {
             Self::SuperiorTo(breakpoint, _) => breakpoint,
             Self::InferiorOrEqualTo(breakpoint) => breakpoint,
+            Self::InferiorTo(breakpoint, _) => breakpoint,
+            Self::SuperiorOrEqualTo(breakpoint) => breakpoint,
+            Self::ContainerInferiorOrEqualTo(breakpoint) => breakpoint,
+            Self::ContainerSuperiorOrEqualTo(breakpoint) => breakpoint,
+        }
+    }
+
+    /// return true if the media query comes from a `layoutNNNpx` attribute,
+    /// false if it scopes the `layout` attribute of a tag having breakpoints
+    pub fn is_breakpoint_attribute(&self) -> bool {
+        !matches!(self, Self::SuperiorTo(_, _) | Self::InferiorTo(_, _))
+    }
+
+    /// return the start of the breakpoint attribute names of the media query,
+    /// `attribute` being the name of the layout attribute
+    pub fn attribute_prefix(&self, attribute: &str) -> String {
+        match self {
+            Self::ContainerInferiorOrEqualTo(_) | Self::ContainerSuperiorOrEqualTo(_) => {
+                format!("{attribute}c")
+            }
+            _ => attribute.to_string(),
+        }
+    }
+
+    /// position of the variant in the stylesheet
+    fn rank(&self) -> usize {
+        match self {
+            Self::InferiorOrEqualTo(_) => 0,
+            Self::SuperiorTo(_, _) => 1,
+            Self::InferiorTo(_, _) => 2,
+            Self::SuperiorOrEqualTo(_) => 3,
+            Self::ContainerInferiorOrEqualTo(_) => 4,
+            Self::ContainerSuperiorOrEqualTo(_) => 5,
         }
     }
 }
@@ -24,35 +73,99 @@ impl PartialOrd for MediaQuery {
 impl Ord for MediaQuery {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
-            // `SuperiorTo` is greater than `InferiorOrEqualTo`
-            (MediaQuery::SuperiorTo(_, _), MediaQuery::InferiorOrEqualTo(_)) => Ordering::Greater,
-            (MediaQuery::InferiorOrEqualTo(_), MediaQuery::SuperiorTo(_, _)) => Ordering::Less,
-
-            //I think in the next two cases it's the same thinkg so we can say in every other case,
-            //compare
-
-            // If both are `SuperiorTo`, compare by usize, so if the same return Equals
+            // desktop first, the biggest breakpoints come first
+            // so the smaller ones override them
             (MediaQuery::SuperiorTo(a, _), MediaQuery::SuperiorTo(b, _)) => b.cmp(a),
-
-            // If both are `InferiorOrEqualTo`, compare by usize, so if the same return Equals
             (MediaQuery::InferiorOrEqualTo(a), MediaQuery::InferiorOrEqualTo(b)) => b.cmp(a),
+            (
+                MediaQuery::ContainerInferiorOrEqualTo(a),
+                MediaQuery::ContainerInferiorOrEqualTo(b),
+            ) => b.cmp(a),
+
+            // mobile first, the smallest breakpoints come first
+            // so the bigger ones override them
+            (MediaQuery::InferiorTo(a, _), MediaQuery::InferiorTo(b, _)) => a.cmp(b),
+            (MediaQuery::SuperiorOrEqualTo(a), MediaQuery::SuperiorOrEqualTo(b)) => a.cmp(b),
+            (
+                MediaQuery::ContainerSuperiorOrEqualTo(a),
+                MediaQuery::ContainerSuperiorOrEqualTo(b),
+            ) => a.cmp(b),
+
+            // `SuperiorTo` is greater than `InferiorOrEqualTo`,
+            // `SuperiorOrEqualTo` is greater than `InferiorTo`,
+            // and container queries come after the media queries
+            _ => self.rank().cmp(&other.rank()),
         }
     }
 }
 
 pub fn extract_breakpoint(input: &str) -> Option<usize> {
-    // Wrong pattern, nothing to extract
-    if input.len() <= "layoutpx".len() {
-        return None;
+    // Slice out the number part, if the pattern is wrong there is nothing to extract
+    let number_part = input.strip_prefix("layout")?.strip_suffix("px")?;
+    number_part.parse::<usize>().ok()
+}
+
+/// return the width of a named breakpoint attribute, like `layout-md`,
+/// None if the attribute is not written this way or if the name is not declared
+pub fn extract_named_breakpoint(input: &str, breakpoints: &BTreeMap<String, usize>) -> Option<usize> {
+    breakpoints.get(input.strip_prefix("layout-")?).copied()
+}
+
+/// return the names of the attributes setting the layout at this breakpoint:
+/// `layout600px` and `layout-<name>` for each named breakpoint of this width
+/// (`layoutc600px` and `layoutc-<name>` with the `layoutc` prefix)
+pub fn breakpoint_attributes(
+    prefix: &str,
+    breakpoint: usize,
+    breakpoints: &BTreeMap<String, usize>,
+) -> Vec<String> {
+    let mut attributes = vec![format!("{prefix}{breakpoint}px")];
+    for (name, width) in breakpoints {
+        if *width == breakpoint {
+            attributes.push(format!("{prefix}-{name}"));
+        }
     }
-    let number_part = &input[6..input.len() - 2]; // Slice out the number part
-    return number_part.parse::<usize>().ok();
+    attributes
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn mobile_first_media_queries_are_sorted_by_increasing_breakpoint() {
+        let mut mqs = vec![
+            MediaQuery::SuperiorOrEqualTo(900),
+            MediaQuery::InferiorTo(600, "p:1".to_string()),
+            MediaQuery::SuperiorOrEqualTo(600),
+        ];
+        mqs.sort();
+        assert_eq!(
+            mqs,
+            vec![
+                MediaQuery::InferiorTo(600, "p:1".to_string()),
+                MediaQuery::SuperiorOrEqualTo(600),
+                MediaQuery::SuperiorOrEqualTo(900),
+            ]
+        );
+    }
+
+    #[test]
+    fn named_breakpoints_are_resolved() {
+        let breakpoints = BTreeMap::from([("md".to_string(), 768), ("tablet".to_string(), 768)]);
+        assert_eq!(extract_named_breakpoint("layout-md", &breakpoints), Some(768));
+        assert_eq!(extract_named_breakpoint("layout-xl", &breakpoints), None);
+        assert_eq!(extract_named_breakpoint("layout768px", &breakpoints), None);
+        assert_eq!(
+            breakpoint_attributes("layout", 768, &breakpoints),
+            vec!["layout768px", "layout-md", "layout-tablet"]
+        );
+        assert_eq!(
+            breakpoint_attributes("layoutc", 768, &breakpoints),
+            vec!["layoutc768px", "layoutc-md", "layoutc-tablet"]
+        );
+    }
+
     #[test]
     fn extract_breakpoint_with_nothing_after_at() {
         let bp = extract_breakpoint("layout@");
@@ -65,6 +178,12 @@ mod tests {
         assert_eq!(bp, Some(600))
     }
 
+    #[test]
+    fn extract_breakpoint_without_px_suffix() {
+        let bp = extract_breakpoint("layout600");
+        assert_eq!(bp, None)
+    }
+
     #[test]
     fn extract_breakpoint_from_correct_mq() {
         let bp = extract_breakpoint("layout@600px");
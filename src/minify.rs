@@ -0,0 +1,453 @@
+/// A piece of a stylesheet, as much as the minifier needs to understand it.
+#[derive(Debug, PartialEq)]
+enum Node {
+    /// `selector { declarations }`
+    Rule {
+        selector: String,
+        declarations: Vec<String>,
+    },
+    /// `@media ... { children }`, or any other at-rule with a block
+    Block {
+        prelude: String,
+        children: Vec<Node>,
+    },
+    /// at-rule without block, like `@layer a, b;`
+    Statement(String),
+}
+
+/// Minify a stylesheet generated by layoutcss:
+/// comments and useless whitespace are removed, the last `;` of each block is dropped,
+/// numbers are written in their shortest form (`0.500rem` becomes `.5rem`)
+/// and rules with the same declarations are merged when it doesn't change the cascade.
+pub fn minify_css(css: &str) -> String {
+    let css = strip_comments(css);
+    let mut chars = css.chars().peekable();
+    let mut nodes = parse_nodes(&mut chars);
+    merge_identical_blocks(&mut nodes);
+    let mut output = String::with_capacity(css.len() / 2);
+    write_nodes(&nodes, &mut output);
+    output
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut output = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        output.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    output.push_str(rest);
+    output
+}
+
+/// read until the end of the current block (or of the text)
+fn parse_nodes(chars: &mut std::iter::Peekable<std::str::Chars>) -> Vec<Node> {
+    let mut nodes = vec![];
+    let mut prelude = String::new();
+    let mut quote: Option<char> = None;
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            prelude.push(c);
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                prelude.push(c);
+            }
+            '}' => break,
+            ';' if prelude.trim_start().starts_with('@') => {
+                nodes.push(Node::Statement(collapse_whitespace(&prelude)));
+                prelude.clear();
+            }
+            '{' => {
+                let head = collapse_whitespace(&prelude);
+                prelude.clear();
+                if head.starts_with('@') {
+                    nodes.push(Node::Block {
+                        prelude: head,
+                        children: parse_nodes(chars),
+                    });
+                } else {
+                    nodes.push(Node::Rule {
+                        selector: minify_selector(&head),
+                        declarations: parse_declarations(chars),
+                    });
+                }
+            }
+            _ => prelude.push(c),
+        }
+    }
+    nodes
+}
+
+/// read the declarations of a rule until its closing `}`
+fn parse_declarations(chars: &mut std::iter::Peekable<std::str::Chars>) -> Vec<String> {
+    let mut declarations = vec![];
+    let mut declaration = String::new();
+    let mut quote: Option<char> = None;
+    let mut parentheses = 0;
+    for c in chars.by_ref() {
+        if let Some(q) = quote {
+            declaration.push(c);
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                declaration.push(c);
+            }
+            '(' => {
+                parentheses += 1;
+                declaration.push(c);
+            }
+            ')' => {
+                parentheses -= 1;
+                declaration.push(c);
+            }
+            ';' if parentheses == 0 => {
+                declarations.extend(minify_declaration(&declaration));
+                declaration.clear();
+            }
+            '}' => break,
+            _ => declaration.push(c),
+        }
+    }
+    declarations.extend(minify_declaration(&declaration));
+    declarations
+}
+
+/// replace every run of whitespace outside of quotes by a single space
+fn collapse_whitespace(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut quote: Option<char> = None;
+    let mut pending_space = false;
+    for c in text.trim().chars() {
+        if quote.is_none() && c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space {
+            output.push(' ');
+            pending_space = false;
+        }
+        match quote {
+            Some(q) if c == q => quote = None,
+            None if c == '"' || c == '\'' => quote = Some(c),
+            _ => {}
+        }
+        output.push(c);
+    }
+    output
+}
+
+/// remove the spaces around the characters given, outside of quotes
+fn remove_spaces_around(text: &str, separators: &[char]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+    let mut quote: Option<char> = None;
+    for (i, c) in chars.iter().enumerate() {
+        match quote {
+            Some(q) if *c == q => quote = None,
+            None if *c == '"' || *c == '\'' => quote = Some(*c),
+            None if *c == ' ' => {
+                let previous = i.checked_sub(1).map(|i| chars[i]);
+                let next = chars.get(i + 1).copied();
+                // `~=` is an attribute operator, not the `~` combinator
+                let next_is_separator =
+                    next.is_some_and(|n| separators.contains(&n) && chars.get(i + 2) != Some(&'='));
+                if previous.is_some_and(|p| separators.contains(&p)) || next_is_separator {
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        output.push(*c);
+    }
+    output
+}
+
+fn minify_selector(selector: &str) -> String {
+    remove_spaces_around(&collapse_whitespace(selector), &[',', '>', '+', '~'])
+}
+
+/// return the declaration as `property:value`, or None if it's empty
+fn minify_declaration(declaration: &str) -> Option<String> {
+    let declaration = collapse_whitespace(declaration);
+    let (property, value) = declaration.split_once(':')?;
+    let value = remove_spaces_around(value.trim(), &[',']);
+    Some(format!("{}:{}", property.trim(), shorten_numbers(&value)))
+}
+
+/// write every number of the value in its shortest form:
+/// no leading zero before the dot and no trailing zeros after it
+fn shorten_numbers(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut rest = value;
+    let mut quote: Option<char> = None;
+    while let Some(c) = rest.chars().next() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let previous = output.chars().last();
+        // an unquoted url is kept as it is, `url(/img/0.50x.png)` is another file than `.5x`
+        if rest.starts_with("url(") && !previous.is_some_and(|p| p.is_alphanumeric() || p == '-') {
+            let url_end = rest.find(')').map_or(rest.len(), |end| end + 1);
+            output.push_str(&rest[..url_end]);
+            rest = &rest[url_end..];
+            continue;
+        }
+        // a number can't start in the middle of a word, like in `h1` or `#0a0a0a`
+        let starts_number = (c.is_ascii_digit() || c == '.')
+            && !previous.is_some_and(|p| p.is_alphanumeric() || p == '-' || p == '#' || p == '.');
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        if starts_number && rest[..number_end].parse::<f64>().is_ok() {
+            output.push_str(&shorten_number(&rest[..number_end]));
+            rest = &rest[number_end..];
+            continue;
+        }
+        if c == '"' || c == '\'' {
+            quote = Some(c);
+        }
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    output
+}
+
+fn shorten_number(number: &str) -> String {
+    let Some((integer, fraction)) = number.split_once('.') else {
+        return number.to_string();
+    };
+    let integer = integer.trim_start_matches('0');
+    let fraction = fraction.trim_end_matches('0');
+    match (integer, fraction) {
+        ("", "") => "0".to_string(),
+        (integer, "") => integer.to_string(),
+        (integer, fraction) => format!("{integer}.{fraction}"),
+    }
+}
+
+/// Merge the rules having exactly the same declarations into the first of them.
+/// A rule is moved up only if no rule in between sets one of its properties
+/// (or a longhand/shorthand of them), otherwise the cascade could change.
+fn merge_identical_blocks(nodes: &mut Vec<Node>) {
+    for node in nodes.iter_mut() {
+        if let Node::Block { children, .. } = node {
+            merge_identical_blocks(children);
+        }
+    }
+    let mut i = 0;
+    while i < nodes.len() {
+        let mut j = i + 1;
+        while j < nodes.len() {
+            let mergeable = match (&nodes[i], &nodes[j]) {
+                (
+                    Node::Rule { declarations, .. },
+                    Node::Rule {
+                        declarations: other,
+                        ..
+                    },
+                ) => {
+                    declarations == other
+                        && nodes[i + 1..j]
+                            .iter()
+                            .all(|between| !sets_one_of(between, declarations))
+                }
+                _ => false,
+            };
+            if mergeable {
+                let Node::Rule {
+                    selector: other, ..
+                } = nodes.remove(j)
+                else {
+                    unreachable!()
+                };
+                if let Node::Rule { selector, .. } = &mut nodes[i] {
+                    selector.push(',');
+                    selector.push_str(&other);
+                }
+            } else {
+                j += 1;
+            }
+        }
+        i += 1;
+    }
+}
+
+/// First words of properties setting the same values as the properties of another family,
+/// like a shorthand and its longhands (`gap` and `column-gap`, `inset` and `top`,
+/// `font` and `line-height`) or a physical property and its logical one (`width` and
+/// `inline-size`). The other properties are in the family of their first word,
+/// so `margin`, `margin-top` and `margin-inline` conflict.
+const PROPERTY_FAMILIES: [(&str, &str); 20] = [
+    ("column", "gap"),
+    ("columns", "gap"),
+    ("row", "gap"),
+    ("grid", "gap"),
+    ("top", "inset"),
+    ("right", "inset"),
+    ("bottom", "inset"),
+    ("left", "inset"),
+    ("justify", "align"),
+    ("place", "align"),
+    ("width", "size"),
+    ("height", "size"),
+    ("min", "size"),
+    ("max", "size"),
+    ("inline", "size"),
+    ("block", "size"),
+    ("aspect", "size"),
+    ("line", "font"),
+    ("white", "text"),
+    ("word", "text"),
+];
+
+/// return the family of the property of the declaration, see PROPERTY_FAMILIES
+fn property_family(declaration: &str) -> &str {
+    let first_word = declaration
+        .trim_start_matches('-')
+        .split([':', '-'])
+        .next()
+        .unwrap_or("");
+    PROPERTY_FAMILIES
+        .iter()
+        .find(|(word, _)| *word == first_word)
+        .map_or(first_word, |(_, family)| family)
+}
+
+/// return true if the two declarations can set the same property
+fn conflicts(declaration: &str, other: &str) -> bool {
+    let (family, other_family) = (property_family(declaration), property_family(other));
+    // `all` resets every property
+    family == other_family || family == "all" || other_family == "all"
+}
+
+/// return true if the node sets one of the properties of the declarations
+fn sets_one_of(node: &Node, declarations: &[String]) -> bool {
+    match node {
+        Node::Rule {
+            declarations: other,
+            ..
+        } => other
+            .iter()
+            .any(|o| declarations.iter().any(|d| conflicts(d, o))),
+        Node::Block { children, .. } => children
+            .iter()
+            .any(|child| sets_one_of(child, declarations)),
+        Node::Statement(_) => false,
+    }
+}
+
+fn write_nodes(nodes: &[Node], output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Rule {
+                selector,
+                declarations,
+            } => {
+                if declarations.is_empty() {
+                    continue;
+                }
+                output.push_str(selector);
+                output.push('{');
+                output.push_str(&declarations.join(";"));
+                output.push('}');
+            }
+            Node::Block { prelude, children } => {
+                output.push_str(prelude);
+                output.push('{');
+                write_nodes(children, output);
+                output.push('}');
+            }
+            Node::Statement(statement) => {
+                output.push_str(statement);
+                output.push(';');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whitespace_and_last_semicolon_are_removed() {
+        let css = "\n  row-l > * ,\n stack-l {\n    display: flex;\n    gap: 1rem;\n}\n";
+        assert_eq!(minify_css(css), "row-l>*,stack-l{display:flex;gap:1rem}");
+    }
+
+    #[test]
+    fn attribute_operators_and_quotes_are_kept() {
+        let css = "row-l[layout~=\"gap:2\"] {\n  gap: 2.61800rem;\n}";
+        assert_eq!(minify_css(css), "row-l[layout~=\"gap:2\"]{gap:2.618rem}");
+    }
+
+    #[test]
+    fn numbers_are_shortened() {
+        assert_eq!(
+            shorten_numbers("0.5rem 1.000rem 0.0 10px"),
+            ".5rem 1rem 0 10px"
+        );
+        assert_eq!(shorten_numbers("#0a0a0a var(--h1)"), "#0a0a0a var(--h1)");
+        assert_eq!(
+            shorten_numbers("calc(100% - 0.50rem)"),
+            "calc(100% - .5rem)"
+        );
+    }
+
+    #[test]
+    fn urls_are_not_shortened() {
+        assert_eq!(
+            shorten_numbers("url(/img/0.50x.png) 0.50rem"),
+            "url(/img/0.50x.png) .5rem"
+        );
+    }
+
+    #[test]
+    fn identical_blocks_are_merged() {
+        let css = "a { color: red; } b { margin: 0; } c { color: red; }";
+        assert_eq!(minify_css(css), "a,c{color:red}b{margin:0}");
+    }
+
+    #[test]
+    fn blocks_are_not_merged_across_a_conflicting_rule() {
+        let css = "a { color: red; } b { color: blue; } c { color: red; }";
+        assert_eq!(minify_css(css), "a{color:red}b{color:blue}c{color:red}");
+    }
+
+    #[test]
+    fn blocks_are_not_merged_across_a_longhand_or_shorthand() {
+        let css = "area-l { gap: 2rem; } row-l[x] { column-gap: 1rem; } row-l[y] { gap: 2rem; }";
+        assert_eq!(
+            minify_css(css),
+            "area-l{gap:2rem}row-l[x]{column-gap:1rem}row-l[y]{gap:2rem}"
+        );
+        let css = "a { inset: 0; } b { top: 1rem; } c { inset: 0; }";
+        assert_eq!(minify_css(css), "a{inset:0}b{top:1rem}c{inset:0}");
+    }
+
+    #[test]
+    fn media_queries_are_kept() {
+        let css =
+            "/* comment */\n@media (width <= 600px) {\n  a { gap: 1rem; }\n  b { gap: 1rem; }\n}\n";
+        assert_eq!(minify_css(css), "@media (width <= 600px){a,b{gap:1rem}}");
+    }
+}
@@ -0,0 +1,139 @@
+use crate::components::COMPONENT_TAGS;
+use crate::config::LayoutStyleConfig;
+
+/// Placeholder of the component name in the element naming pattern.
+pub const NAME_PLACEHOLDER: &str = "{name}";
+
+/// How the components are named in the markup, `{name}-l` by default
+/// (`row-l`, `stack-l`...), but it can be `lc-{name}` or `x-{name}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementNaming {
+    prefix: String,
+    suffix: String,
+}
+
+impl Default for ElementNaming {
+    fn default() -> Self {
+        ElementNaming {
+            prefix: String::new(),
+            suffix: "-l".to_string(),
+        }
+    }
+}
+
+impl ElementNaming {
+    /// Create the naming from a pattern like `lc-{name}`, return None if the pattern
+    /// doesn't contain `{name}` once or if it doesn't give valid custom element names.
+    pub fn new(pattern: &str) -> Option<Self> {
+        let (prefix, suffix) = pattern.split_once(NAME_PLACEHOLDER)?;
+        let affixes = format!("{prefix}{suffix}");
+        let valid = affixes.contains('-')
+            && affixes
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+            && !prefix.starts_with(|c: char| !c.is_ascii_lowercase());
+        valid.then(|| ElementNaming {
+            prefix: prefix.to_string(),
+            suffix: suffix.to_string(),
+        })
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// return the name of the component in this naming, `row` gives `lc-row`
+    pub fn element_name(&self, component_name: &str) -> String {
+        format!("{}{component_name}{}", self.prefix, self.suffix)
+    }
+
+    /// return true if the tag is named like the components, like `lc-table` for `lc-{name}`
+    pub fn is_element_name(&self, tag: &str) -> bool {
+        tag.len() > self.prefix.len() + self.suffix.len()
+            && tag.starts_with(&self.prefix)
+            && tag.ends_with(&self.suffix)
+    }
+
+    /// return the default tag name of the component named `tag` in this naming,
+    /// `lc-row` gives `row-l`, and None if the tag is not a component
+    pub fn component_tag(&self, tag: &str) -> Option<&'static str> {
+        let name = tag.strip_prefix(&self.prefix)?.strip_suffix(&self.suffix)?;
+        COMPONENT_TAGS
+            .into_iter()
+            .find(|component_tag| component_tag.strip_suffix("-l") == Some(name))
+    }
+}
+
+/// Names the generated css selects the elements with, taken from the config:
+/// the layout attribute, `layout` by default, and the naming of the components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorNames {
+    pub attribute: String,
+    pub element_naming: ElementNaming,
+}
+
+impl Default for SelectorNames {
+    fn default() -> Self {
+        SelectorNames {
+            attribute: "layout".to_string(),
+            element_naming: ElementNaming::default(),
+        }
+    }
+}
+
+impl SelectorNames {
+    pub fn from_config(layout_style_config: &LayoutStyleConfig) -> Self {
+        SelectorNames {
+            attribute: layout_style_config.attribute_name.clone(),
+            // the config only accepts valid patterns
+            element_naming: ElementNaming::new(&layout_style_config.element_name)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// return the tag name of the component, `row` gives `row-l` by default
+    pub fn tag(&self, component_name: &str) -> String {
+        self.element_naming.element_name(component_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_must_give_custom_element_names() {
+        assert!(ElementNaming::new("lc-{name}").is_some());
+        assert!(ElementNaming::new("{name}-l").unwrap().is_default());
+        assert_eq!(ElementNaming::new("{name}"), None);
+        assert_eq!(ElementNaming::new("lc-row"), None);
+        assert_eq!(ElementNaming::new("1-{name}"), None);
+        assert_eq!(ElementNaming::new("Lc-{name}"), None);
+    }
+
+    #[test]
+    fn rules_select_the_configured_component_names() {
+        let config = LayoutStyleConfig::from_toml_str("element_name = \"lc-{name}\"").unwrap();
+        let (css, _) = crate::get_css_and_diagnostics(
+            "<lc-row layout=\"gap:2\" layout600px=\"gap:1\">",
+            None,
+            None,
+            &config,
+        );
+        assert!(css.contains("lc-row{"));
+        assert!(css.contains("lc-row[layout600px=\"gap:1\"][layout~=\"gap:2\"]"));
+        assert!(css.contains("lc-row[layout600px~=\"gap:1\"]"));
+        assert!(!css.contains("row-l"));
+    }
+
+    #[test]
+    fn component_tags_are_found_in_the_naming() {
+        let naming = ElementNaming::new("lc-{name}").unwrap();
+        assert_eq!(naming.component_tag("lc-row"), Some("row-l"));
+        assert_eq!(naming.component_tag("row-l"), None);
+        assert_eq!(naming.component_tag("lc-table"), None);
+        assert!(naming.is_element_name("lc-table"));
+        assert!(!naming.is_element_name("lc-"));
+        assert!(!naming.is_element_name("row-l"));
+    }
+}
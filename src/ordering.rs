@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+use crate::components::COMPONENT_TAGS;
+use crate::dev::DEV_ELEMENTS_CSS;
+use crate::naming::ElementNaming;
+use crate::reset::RESET_CSS;
+use crate::specificity::rule_specificity;
+
+/// Group of a css rule in the stylesheet, the groups are written in this order.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum RuleGroup {
+    Reset,
+    Dev,
+    /// the style every component gets, like `row-l{display:flex;...}`,
+    /// with the index of the component in COMPONENT_TAGS
+    ComponentBase(usize),
+    /// the style added by a class of the component, like `row-l[layout~="gap:2"]`
+    ComponentModifier(usize),
+    Utility,
+}
+
+/// Cascade layers of the stylesheet when it's written in layers, in priority order,
+/// the breakpoint overrides come last so they still win over the base rules.
+pub const LAYERS: [&str; 4] = [
+    "layoutcss.reset",
+    "layoutcss.components",
+    "layoutcss.utilities",
+    "layoutcss.breakpoints",
+];
+
+impl RuleGroup {
+    /// return the cascade layer of the rules of this group (the dev outlines go with the reset)
+    pub fn layer(&self) -> &'static str {
+        match self {
+            RuleGroup::Reset | RuleGroup::Dev => LAYERS[0],
+            RuleGroup::ComponentBase(_) | RuleGroup::ComponentModifier(_) => LAYERS[1],
+            RuleGroup::Utility => LAYERS[2],
+        }
+    }
+}
+
+/// return the group of the rule by looking at how its first selector starts,
+/// the components being named with the naming
+pub fn rule_group(rule: &str, element_naming: &ElementNaming) -> RuleGroup {
+    if rule.starts_with(RESET_CSS) {
+        return RuleGroup::Reset;
+    }
+    if rule.ends_with(DEV_ELEMENTS_CSS) {
+        return RuleGroup::Dev;
+    }
+    let rule = rule.trim_start();
+    let tag_end = rule
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(rule.len());
+    let component = element_naming
+        .component_tag(&rule[..tag_end])
+        .and_then(|tag| COMPONENT_TAGS.iter().position(|component_tag| *component_tag == tag));
+    match component {
+        Some(index) if rule[tag_end..].starts_with('[') => RuleGroup::ComponentModifier(index),
+        Some(index) => RuleGroup::ComponentBase(index),
+        None => RuleGroup::Utility,
+    }
+}
+
+/// return the rules sorted by group, and alphabetically inside a group,
+/// so the same rules always give the same stylesheet
+pub fn sorted_rules<'a>(
+    rules: &'a HashSet<String>,
+    element_naming: &ElementNaming,
+) -> Vec<&'a String> {
+    let mut sorted: Vec<&String> = rules.iter().collect();
+    sorted.sort_by_cached_key(|rule| (rule_group(rule, element_naming), *rule));
+    sorted
+}
+
+/// Same as sorted_rules, but the rules of a group go from the least to the most specific,
+/// for the low specificity output: once their specificity is removed, the last rule wins,
+/// like `icon-l[layout*="gap-dir:end"][layout*="gap:1"]` over `icon-l[layout*="gap:1"]`.
+pub fn sorted_rules_by_specificity<'a>(
+    rules: &'a HashSet<String>,
+    element_naming: &ElementNaming,
+) -> Vec<&'a String> {
+    let mut sorted: Vec<&String> = rules.iter().collect();
+    sorted.sort_by_cached_key(|rule| {
+        (rule_group(rule, element_naming), rule_specificity(rule), *rule)
+    });
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_are_grouped() {
+        let naming = ElementNaming::default();
+        assert_eq!(
+            rule_group(&format!("{RESET_CSS}\nhtml{{}}"), &naming),
+            RuleGroup::Reset
+        );
+        assert_eq!(
+            rule_group("\n  row-l{\n display:flex;}", &naming),
+            RuleGroup::ComponentBase(6)
+        );
+        assert_eq!(
+            rule_group("row-l[layout~=\"gap:2\"]{}", &naming),
+            RuleGroup::ComponentModifier(6)
+        );
+        assert_eq!(rule_group("[layout~=\"p:2\"]{}", &naming), RuleGroup::Utility);
+        let naming = ElementNaming::new("lc-{name}").unwrap();
+        assert_eq!(
+            rule_group("lc-row[layout~=\"gap:2\"]{}", &naming),
+            RuleGroup::ComponentModifier(6)
+        );
+        assert_eq!(rule_group("row-l{}", &naming), RuleGroup::Utility);
+    }
+
+    #[test]
+    fn groups_are_put_in_their_layer() {
+        let naming = ElementNaming::default();
+        assert_eq!(RuleGroup::Dev.layer(), "layoutcss.reset");
+        assert_eq!(
+            rule_group("row-l[layout~=\"gap:2\"]{}", &naming).layer(),
+            "layoutcss.components"
+        );
+        assert_eq!(
+            rule_group("[layout~=\"p:2\"]{}", &naming).layer(),
+            "layoutcss.utilities"
+        );
+    }
+
+    #[test]
+    fn more_specific_rules_come_last_in_their_group() {
+        let rules: HashSet<String> = [
+            "[layout~=\"p:2\"]{}",
+            "icon-l[layout*=\"gap:1\"] >:nth-child(1){}",
+            "icon-l[layout*=\"gap-dir:end\"][layout*=\"gap:1\"] >:nth-child(1){}",
+            "icon-l{}",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let sorted: Vec<&str> = sorted_rules_by_specificity(&rules, &ElementNaming::default())
+            .into_iter()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            sorted,
+            vec![
+                "icon-l{}",
+                "icon-l[layout*=\"gap:1\"] >:nth-child(1){}",
+                "icon-l[layout*=\"gap-dir:end\"][layout*=\"gap:1\"] >:nth-child(1){}",
+                "[layout~=\"p:2\"]{}",
+            ]
+        );
+    }
+
+    #[test]
+    fn sorted_rules_follow_the_groups_then_the_text() {
+        let rules: HashSet<String> = [
+            "[layout~=\"p:2\"]{}",
+            "stack-l[layout~=\"gap:2\"]{}",
+            "row-l[layout~=\"gap:2\"]{}",
+            "row-l[layout~=\"gap:1\"]{}",
+            "stack-l{}",
+            "row-l{}",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let sorted: Vec<&str> = sorted_rules(&rules, &ElementNaming::default())
+            .into_iter()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            sorted,
+            vec![
+                "row-l{}",
+                "stack-l{}",
+                "row-l[layout~=\"gap:1\"]{}",
+                "row-l[layout~=\"gap:2\"]{}",
+                "stack-l[layout~=\"gap:2\"]{}",
+                "[layout~=\"p:2\"]{}",
+            ]
+        );
+    }
+}
@@ -1,11 +1,23 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use serde::{Deserialize, Serialize};
 use State::*;
 
 use crate::{
     builder::{generate, LayoutElement},
-    media_query::{extract_breakpoint, MediaQuery},
+    class_mode::prefixed_class_equivalent,
+    components::COMPONENT_TAGS,
+    classes::LayoutClass,
+    config::LayoutStyleConfig,
+    diagnostic::{Diagnostic, DiagnosticKind},
+    expression::{closing_brace, plain_literal, string_literals},
+    media_query::{extract_breakpoint, extract_named_breakpoint, MediaQuery},
+    naming::ElementNaming,
+    span::{tokens_with_offsets, LineIndex, Span},
 };
 
+/// Elements whose content is read as text, so it's never parsed as markup.
+const RAW_TEXT_ELEMENTS: [&str; 3] = ["script", "style", "textarea"];
+
 #[derive(Debug, PartialEq)]
 pub enum State {
     Resting,
@@ -15,6 +27,23 @@ pub enum State {
     ReadingAttributeName,
     WaitingAttributeValue,
     ReadingAttributeValue,
+    ReadingSingleQuotedAttributeValue,
+    ReadingUnquotedAttributeValue,
+    /// a javascript expression between braces, like `layout={cond ? "p:1" : "p:2"}`
+    ReadingExpressionAttributeValue,
+}
+
+/// Where a layout element has been found in the text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementSource<'a> {
+    pub element: LayoutElement<'a>,
+    /// the tag name of the element
+    pub tag: Span,
+    /// the value of the layout attribute which produced the element
+    pub attribute: Option<Span>,
+    /// the class producing the element, None for components
+    /// because they are produced by the tag and all the classes applied to it
+    pub token: Option<Span>,
 }
 
 pub struct Parser<'a> {
@@ -28,8 +57,34 @@ pub struct Parser<'a> {
     pub layout_attribute_value_end: Option<usize>,
     pub layout_breakpoint_attribute_value_start: Option<usize>,
     pub layout_breakpoint_attribute_value_end: Option<usize>,
+    /// the biggest breakpoint of the tag (the smallest in mobile first),
+    /// the `layout` attribute applies beyond it
     pub biggest_breakpoint: Option<usize>,
+    /// the value of the breakpoint attribute of biggest_breakpoint,
+    /// empty when it's an expression whose value is only known at runtime
     pub biggest_breakpoint_value: Option<&'a str>,
+    /// true if the `layout` attribute of the tag is an expression
+    pub layout_attribute_is_expression: bool,
+    /// `layoutNNNpx` attributes apply from NNNpx up instead of up to NNNpx
+    pub mobile_first: bool,
+    /// name of the layout attribute, the breakpoint attributes start with it too
+    pub layout_attribute: String,
+    /// how the components are named in the markup
+    pub element_naming: ElementNaming,
+    /// the components and their classes are read from the `class` attribute,
+    /// like `<div class="row-l gap:2">`, and breakpoint attributes are ignored,
+    /// the prefixed form `l-row l-gap-2` is reported instead of read
+    pub class_mode: bool,
+    /// widths of the named breakpoints, `layout-md` is resolved with the width of `md`
+    pub breakpoints: BTreeMap<String, usize>,
+    /// problems found in the markup since the creation of the parser
+    pub diagnostics: Vec<Diagnostic>,
+    /// every layout element generated, in the order of the text,
+    /// with its position (an element is present once for each occurrence)
+    pub sources: Vec<ElementSource<'a>>,
+    line_index: LineIndex,
+    /// index of the text where the parsing goes on after a skipped section
+    resume_at: usize,
 }
 
 impl<'a> Parser<'a> {
@@ -47,9 +102,31 @@ impl<'a> Parser<'a> {
             layout_breakpoint_attribute_value_end: None,
             biggest_breakpoint: None,
             biggest_breakpoint_value: None,
+            layout_attribute_is_expression: false,
+            mobile_first: false,
+            layout_attribute: "layout".to_string(),
+            element_naming: ElementNaming::default(),
+            class_mode: false,
+            breakpoints: BTreeMap::new(),
+            diagnostics: Vec::new(),
+            sources: Vec::new(),
+            line_index: LineIndex::new(text),
+            resume_at: 0,
         }
     }
 
+    /// create a parser reading the text as described by the config
+    pub fn with_config(text: &'a str, layout_style_config: &LayoutStyleConfig) -> Self {
+        let mut parser = Parser::new(text);
+        parser.mobile_first = layout_style_config.mobile_first;
+        parser.breakpoints = layout_style_config.breakpoints.clone();
+        parser.layout_attribute = layout_style_config.attribute_name.clone();
+        parser.element_naming =
+            ElementNaming::new(&layout_style_config.element_name).unwrap_or_default();
+        parser.class_mode = layout_style_config.class_mode;
+        parser
+    }
+
     pub fn reset_indexes(&mut self) {
         self.tag_name_start = None;
         self.tag_name_end = None;
@@ -61,6 +138,7 @@ impl<'a> Parser<'a> {
         self.layout_breakpoint_attribute_value_end = None;
         self.biggest_breakpoint = None;
         self.biggest_breakpoint_value = None;
+        self.layout_attribute_is_expression = false;
     }
 
     pub fn tag_name(&self) -> Option<&'a str> {
@@ -108,11 +186,113 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// return the span of text[start..end]
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        self.line_index.span(self.text, start, end)
+    }
+
+    /// call generate for the current tag and the layout attribute value starting at `value_start`,
+    /// and keep where the generated elements and diagnostics come from
+    fn generate_with_sources(
+        &mut self,
+        value: Option<&'a str>,
+        value_start: Option<usize>,
+        media_query: Option<MediaQuery>,
+        elements: &mut HashSet<LayoutElement<'a>>,
+    ) {
+        let (Some(tag_start), Some(tag_end)) = (self.tag_name_start, self.tag_name_end) else {
+            return;
+        };
+        let tag = self.span(tag_start, tag_end + 1);
+        let attribute = value_start.map(|start| self.span(start, start + value.map_or(0, str::len)));
+        let tokens: Vec<(usize, &'a str)> = match (value, value_start) {
+            (Some(value), Some(start)) => tokens_with_offsets(value)
+                .map(|(offset, token)| (start + offset, token))
+                .collect(),
+            _ => vec![],
+        };
+
+        // the components are known by their default names,
+        // a tag named like a component in another naming is not a component
+        let tag_name = self.tag_name_new();
+        let generated_tag_name = if self.class_mode {
+            // the component is the first class naming one, whatever the tag is
+            tokens
+                .iter()
+                .find_map(|(_, token)| self.element_naming.component_tag(token))
+                .unwrap_or("")
+        } else {
+            match self.element_naming.component_tag(tag_name) {
+                Some(component_tag) => component_tag,
+                None if COMPONENT_TAGS.contains(&tag_name) => "",
+                None => tag_name,
+            }
+        };
+        let first_new_diagnostic = self.diagnostics.len();
+        let mut generated = HashSet::new();
+        generate(
+            generated_tag_name,
+            value,
+            media_query,
+            &mut generated,
+            &mut self.diagnostics,
+        );
+        if self.class_mode {
+            // the class attribute is shared with other stylesheets,
+            // so unknown classes and the component class are not problems,
+            // except the prefixed form like `l-gap-2` which is not read
+            let new_diagnostics = self.diagnostics.split_off(first_new_diagnostic);
+            for mut diagnostic in new_diagnostics {
+                if self.element_naming.component_tag(&diagnostic.token).is_some() {
+                    continue;
+                }
+                if diagnostic.kind == DiagnosticKind::UnknownClass {
+                    match prefixed_class_equivalent(&diagnostic.token, &self.element_naming) {
+                        Some(class) => diagnostic.kind = DiagnosticKind::PrefixedClass { class },
+                        None => continue,
+                    }
+                }
+                self.diagnostics.push(diagnostic);
+            }
+        }
+
+        // diagnostics are pushed in the same order as the tokens
+        let mut remaining_tokens = tokens.iter();
+        for diagnostic in &mut self.diagnostics[first_new_diagnostic..] {
+            diagnostic.tag_name = tag_name.to_string();
+            if let Some((start, token)) = remaining_tokens.find(|(_, token)| *token == diagnostic.token) {
+                diagnostic.span = Some(self.line_index.span(self.text, *start, start + token.len()));
+            }
+        }
+
+        for element in generated {
+            let token = match &element {
+                LayoutElement::LayoutUtility(class, _) => tokens
+                    .iter()
+                    .find(|(_, token)| LayoutClass::try_from(*token).as_ref() == Ok(class))
+                    .map(|(start, token)| self.span(*start, start + token.len())),
+                LayoutElement::LayoutComponent(..) => None,
+            };
+            self.sources.push(ElementSource {
+                element: element.clone(),
+                tag,
+                attribute,
+                token,
+            });
+            elements.insert(element);
+        }
+    }
+
     /// update the biggest_breakpoint of the parser only if the new breakpoint is superior
-    /// to the one of the parser or if the parser has None as biggest_breakpoint
+    /// (inferior in mobile first) to the one of the parser or if the parser has None as biggest_breakpoint
     pub fn update_biggest_breakpoint(&mut self, breakpoint: usize) -> bool {
         if let Some(parser_biggest_breakpoint) = self.biggest_breakpoint {
-            if breakpoint > parser_biggest_breakpoint {
+            let beyond = if self.mobile_first {
+                breakpoint < parser_biggest_breakpoint
+            } else {
+                breakpoint > parser_biggest_breakpoint
+            };
+            if beyond {
                 self.biggest_breakpoint = Some(breakpoint);
                 return true;
             }
@@ -123,6 +303,217 @@ impl<'a> Parser<'a> {
         false
     }
 
+    /// return the values to generate the css from, with their start in the text:
+    /// the value itself, or the strings of the expression when the value is an expression
+    fn candidate_values(
+        &self,
+        value: &'a str,
+        value_start: usize,
+        is_expression: bool,
+    ) -> Vec<(usize, &'a str)> {
+        if is_expression {
+            string_literals(value)
+                .into_iter()
+                .map(|(offset, literal)| (value_start + offset, literal))
+                .collect()
+        } else {
+            vec![(value_start, value)]
+        }
+    }
+
+    /// return what follows the layout attribute name in the name of the current attribute:
+    /// "" for `layout` itself, "600px" for `layout600px`, None if it's not a layout attribute
+    fn layout_attribute_suffix(&self) -> Option<&'a str> {
+        let attribute_name = self.attribute_name()?;
+        if self.class_mode {
+            return (attribute_name == "class").then_some("");
+        }
+        attribute_name
+            .strip_prefix(self.layout_attribute.as_str())
+            .filter(|suffix| is_layout_attribute_suffix(suffix, !self.breakpoints.is_empty()))
+    }
+
+    /// remember where the value of the attribute starts, if it's a layout attribute
+    fn start_attribute_value(&mut self, value_start: usize) {
+        match self.layout_attribute_suffix() {
+            Some("") => self.layout_attribute_value_start = Some(value_start),
+            Some(_) => self.layout_breakpoint_attribute_value_start = Some(value_start),
+            None => {}
+        }
+    }
+
+    /// called when the value of an attribute ends at `value_end` (included),
+    /// generate the elements of the breakpoint attributes
+    fn end_attribute_value(&mut self, value_end: usize, elements: &mut HashSet<LayoutElement<'a>>) {
+        if let (Some(attribute_name), Some(suffix)) =
+            (self.attribute_name(), self.layout_attribute_suffix())
+        {
+            if suffix.is_empty() {
+                self.layout_attribute_value_end = Some(value_end);
+                self.layout_attribute_is_expression = self.state == ReadingExpressionAttributeValue;
+                // when we are processing a media query layout attribute
+                // we should call generate too but with a MediaQuery
+                // as parameter
+            } else {
+                self.layout_breakpoint_attribute_value_end = Some(value_end);
+                // `layoutc600px` works like `layout600px`
+                // but with the width of the parent container
+                let (is_container, suffix) = match suffix.strip_prefix('c') {
+                    Some(rest) => (true, rest),
+                    None => (false, suffix),
+                };
+                // the breakpoint is read from the name it would have with the default attribute
+                let viewport_attribute = format!("layout{suffix}");
+                let breakpoint = extract_breakpoint(&viewport_attribute).or_else(|| {
+                    extract_named_breakpoint(&viewport_attribute, &self.breakpoints)
+                });
+                if let (Some(breakpoint), Some(attribute_value), Some(value_start)) = (
+                    breakpoint,
+                    self.layout_breakpoint_attribute_value(),
+                    self.layout_breakpoint_attribute_value_start,
+                ) {
+                    let values = self.candidate_values(
+                        attribute_value,
+                        value_start,
+                        self.state == ReadingExpressionAttributeValue,
+                    );
+                    // the layout attribute is scoped by viewport breakpoints only,
+                    // container ones just override it when they match
+                    if !is_container {
+                        let new_biggest_breakpoint_found =
+                            self.update_biggest_breakpoint(breakpoint);
+                        if new_biggest_breakpoint_found {
+                            // the strings of an expression are only parts of its value
+                            self.biggest_breakpoint_value =
+                                Some(match self.state == ReadingExpressionAttributeValue {
+                                    true => plain_literal(attribute_value).unwrap_or(""),
+                                    false => attribute_value,
+                                });
+                        }
+                    }
+                    // because it's a media-query layout attribute we know it will be InferioOrEqualTo
+                    // (SuperiorOrEqualTo in mobile first)
+                    let mq_new = match (is_container, self.mobile_first) {
+                        (false, false) => MediaQuery::InferiorOrEqualTo(breakpoint),
+                        (false, true) => MediaQuery::SuperiorOrEqualTo(breakpoint),
+                        (true, false) => MediaQuery::ContainerInferiorOrEqualTo(breakpoint),
+                        (true, true) => MediaQuery::ContainerSuperiorOrEqualTo(breakpoint),
+                    };
+                    for (start, value) in values {
+                        let mq = Some(mq_new.clone());
+                        self.generate_with_sources(Some(value), Some(start), mq, elements);
+                    }
+                } else if breakpoint.is_none() {
+                    let kind = if viewport_attribute.starts_with("layout-") {
+                        DiagnosticKind::UnknownBreakpoint
+                    } else {
+                        DiagnosticKind::MalformedBreakpoint {
+                            attribute: self.layout_attribute.clone(),
+                        }
+                    };
+                    self.push_attribute_diagnostic(kind, attribute_name);
+                }
+            }
+        } else if let Some(attribute_name) = self.attribute_name().filter(|_| self.class_mode) {
+            // the classes have no breakpoints, the breakpoint attributes do nothing
+            let is_breakpoint_attribute = attribute_name
+                .strip_prefix(self.layout_attribute.as_str())
+                .is_some_and(|suffix| {
+                    !suffix.is_empty()
+                        && is_layout_attribute_suffix(suffix, !self.breakpoints.is_empty())
+                });
+            if is_breakpoint_attribute {
+                self.push_attribute_diagnostic(DiagnosticKind::IgnoredInClassMode, attribute_name);
+            }
+        }
+    }
+
+    /// report a problem with the name of the current attribute
+    fn push_attribute_diagnostic(&mut self, kind: DiagnosticKind, attribute_name: &str) {
+        let mut diagnostic = Diagnostic::new(kind, self.tag_name_new(), attribute_name);
+        if let (Some(start), Some(end)) = (self.attribute_name_start, self.attribute_name_end) {
+            diagnostic.span = Some(self.span(start, end + 1));
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// if a comment or a CDATA section starts at `start`,
+    /// return the index following its end (or the end of the text if it's not closed)
+    fn skipped_section_end(&self, start: usize) -> Option<usize> {
+        let rest = &self.text[start..];
+        let (opening, closing) = [("<!--", "-->"), ("<![CDATA[", "]]>")]
+            .into_iter()
+            .find(|(opening, _)| rest.starts_with(opening))?;
+        Some(match rest[opening.len()..].find(closing) {
+            Some(end) => start + opening.len() + end + closing.len(),
+            None => self.text.len(),
+        })
+    }
+
+    /// called when the tag being read ends before `content_start`,
+    /// if it opens a raw text element, return the index of its closing tag,
+    /// otherwise return `content_start`
+    fn raw_text_end(&self, content_start: usize) -> usize {
+        let (Some(tag_name), Some(tag_name_start)) = (self.tag_name(), self.tag_name_start) else {
+            return content_start;
+        };
+        let is_closing_tag = self.text[..tag_name_start].ends_with('/');
+        let is_self_closing = self.text[..content_start].ends_with("/>");
+        let tag_name = tag_name.to_ascii_lowercase();
+        if is_closing_tag || is_self_closing || !RAW_TEXT_ELEMENTS.contains(&tag_name.as_str()) {
+            return content_start;
+        }
+        // the closing tag is searched case insensitively, without copying the rest of the text
+        let content = &self.text.as_bytes()[content_start..];
+        let mut search_start = 0;
+        while let Some(end) = self.text[content_start + search_start..].find("</") {
+            let name_start = search_start + end + 2;
+            let name_end = name_start + tag_name.len();
+            // the name must end there, `</scripts>` doesn't close a script
+            if content[name_start..]
+                .get(..tag_name.len())
+                .is_some_and(|name| name.eq_ignore_ascii_case(tag_name.as_bytes()))
+                && content
+                    .get(name_end)
+                    .is_none_or(|c| c.is_ascii_whitespace() || *c == b'/' || *c == b'>')
+            {
+                return content_start + search_start + end;
+            }
+            search_start = name_start;
+        }
+        self.text.len()
+    }
+
+    /// generate the elements of the `layout` attribute of the tag (or of the tag alone),
+    /// scoped by the biggest breakpoint if the tag has breakpoint attributes
+    fn generate_layout_attribute(&mut self, elements: &mut HashSet<LayoutElement<'a>>) {
+        let layout_value = self.layout_attribute_value();
+        let values: Vec<(Option<usize>, Option<&'a str>)> =
+            match (layout_value, self.layout_attribute_value_start) {
+                (Some(value), Some(start)) => self
+                    .candidate_values(value, start, self.layout_attribute_is_expression)
+                    .into_iter()
+                    .map(|(start, value)| (Some(start), Some(value)))
+                    .collect(),
+                _ => vec![(self.layout_attribute_value_start, layout_value)],
+            };
+        let mq = if let (Some(biggest_breakpoint), Some(breakpoint_value)) =
+            (self.biggest_breakpoint, self.biggest_breakpoint_value)
+        {
+            let breakpoint_value = breakpoint_value.to_string();
+            if self.mobile_first {
+                Some(MediaQuery::InferiorTo(biggest_breakpoint, breakpoint_value))
+            } else {
+                Some(MediaQuery::SuperiorTo(biggest_breakpoint, breakpoint_value))
+            }
+        } else {
+            None
+        };
+        for (start, value) in values {
+            self.generate_with_sources(value, start, mq.clone(), elements);
+        }
+    }
+
     /// return the new state of the parser (without changing the parser's state) based on the current state and input character,
     /// if None is returned the state hasn't changed
     pub fn transition(&self, c: char) -> Option<State> {
@@ -133,11 +524,23 @@ impl<'a> Parser<'a> {
             (AfterTagName, c) if c.is_alphabetic() => Some(ReadingAttributeName),
             (ReadingAttributeName, c) if c.is_whitespace() => Some(AfterTagName),
             (ReadingAttributeName, '=') => Some(WaitingAttributeValue),
-            //TODO curly braces counter
             (WaitingAttributeValue, '"') => Some(ReadingAttributeValue),
-            (WaitingAttributeValue, c) if c !='"' => Some(AfterTagName),
+            (WaitingAttributeValue, '\'') => Some(ReadingSingleQuotedAttributeValue),
+            (WaitingAttributeValue, '{') => Some(ReadingExpressionAttributeValue),
+            (WaitingAttributeValue, c) if c.is_whitespace() => Some(AfterTagName),
+            (WaitingAttributeValue, c) if c != '>' => Some(ReadingUnquotedAttributeValue),
             (ReadingAttributeValue, '"') => Some(AfterTagName),
-            (AfterTagName | ReadingTagName | ReadingAttributeName, '>') => Some(Resting),
+            (ReadingSingleQuotedAttributeValue, '\'') => Some(AfterTagName),
+            (ReadingExpressionAttributeValue, '}') => Some(AfterTagName),
+            (ReadingUnquotedAttributeValue, c) if c.is_whitespace() => Some(AfterTagName),
+            (
+                AfterTagName
+                | ReadingTagName
+                | ReadingAttributeName
+                | WaitingAttributeValue
+                | ReadingUnquotedAttributeValue,
+                '>',
+            ) => Some(Resting),
             _ => None,
         }
     }
@@ -146,6 +549,16 @@ impl<'a> Parser<'a> {
     /// add it to the set passed in parameter.
     pub fn parse(&mut self, elements: &mut HashSet<LayoutElement<'a>>) {
         for (i, c) in self.text.char_indices() {
+            // content which is not markup (comments, scripts...) is skipped
+            if i < self.resume_at {
+                continue;
+            }
+            if self.state == Resting && c == '<' {
+                if let Some(end) = self.skipped_section_end(i) {
+                    self.resume_at = end;
+                    continue;
+                }
+            }
             let new_state = self.transition(c);
             // if we enter here, the state has changed
             if let Some(state) = new_state {
@@ -156,46 +569,23 @@ impl<'a> Parser<'a> {
                     (ReadingAttributeName, AfterTagName | WaitingAttributeValue) => {
                         self.attribute_name_end = Some(i - 1)
                     }
-                    (_, ReadingAttributeValue) => {
-                        if let Some(attribute_name) = self.attribute_name() {
-                            if attribute_name == "layout" {
-                                self.layout_attribute_value_start = Some(i + 1);
-                            } else if attribute_name.starts_with("layout") {
-                                self.layout_breakpoint_attribute_value_start = Some(i + 1);
-                            }
-                        }
+                    // the quote is not part of the value, but the first character of an unquoted value is
+                    (_, ReadingAttributeValue | ReadingSingleQuotedAttributeValue) => {
+                        self.start_attribute_value(i + 1)
                     }
-                    (ReadingAttributeValue, AfterTagName) => {
-                        if let Some(attribute_name) = self.attribute_name() {
-                            if attribute_name == "layout" {
-                                self.layout_attribute_value_end = Some(i - 1);
-                                // when we are processing a media query layout attribute
-                                // we should call generate too but with a MediaQuery
-                                // as parameter
-                            } else if attribute_name.starts_with("layout") {
-                                self.layout_breakpoint_attribute_value_end = Some(i - 1);
-                                if let (Some(breakpoint), Some(attribute_value)) = (
-                                    extract_breakpoint(attribute_name),
-                                    self.layout_breakpoint_attribute_value(),
-                                ) {
-                                    let new_biggest_breakpoint_found =
-                                        self.update_biggest_breakpoint(breakpoint);
-                                    if new_biggest_breakpoint_found {
-                                        self.biggest_breakpoint_value =
-                                            self.layout_breakpoint_attribute_value();
-                                    }
-                                    // because it's a media-query layout attribute we know it will be InferioOrEqualTo
-                                    let mq_new = MediaQuery::InferiorOrEqualTo(breakpoint);
-                                    generate(
-                                        self.tag_name_new(),
-                                        Some(attribute_value),
-                                        Some(mq_new),
-                                        elements,
-                                    );
-                                }
-                            }
-                        }
+                    (_, ReadingUnquotedAttributeValue) => self.start_attribute_value(i),
+                    // the expression is read at once, the parser goes on at its closing brace
+                    (_, ReadingExpressionAttributeValue) => {
+                        self.start_attribute_value(i + 1);
+                        self.resume_at = closing_brace(self.text, i);
                     }
+                    (
+                        ReadingAttributeValue
+                        | ReadingSingleQuotedAttributeValue
+                        | ReadingUnquotedAttributeValue
+                        | ReadingExpressionAttributeValue,
+                        AfterTagName,
+                    ) => self.end_attribute_value(i - 1, elements),
 
                     // when we are leaving a tag, we generate layout elements
                     // and we reset the indexes of the parser
@@ -206,19 +596,15 @@ impl<'a> Parser<'a> {
                         if current_state == &ReadingTagName {
                             self.tag_name_end = Some(i - 1);
                         }
-                        if let (Some(tag_name), layout_value) =
-                            (self.tag_name(), self.layout_attribute_value())
-                        {
-                            let mq = if let (Some(biggest_breakpoint), Some(breakpoint_value)) = (
-                                self.biggest_breakpoint,
-                                self.biggest_breakpoint_value,
-                            ) {
-                                Some(MediaQuery::SuperiorTo(biggest_breakpoint, breakpoint_value.to_string()))
-                            } else {
-                                None
-                            };
-                            generate(tag_name, layout_value, mq, elements);
+                        // an unquoted value is ended by the end of the tag
+                        if current_state == &ReadingUnquotedAttributeValue {
+                            self.end_attribute_value(i - 1, elements);
+                        }
+                        if self.tag_name().is_some() {
+                            self.generate_layout_attribute(elements);
                         }
+                        // the content of <script>, <style> and <textarea> is text, not markup
+                        self.resume_at = self.raw_text_end(i + 1);
                         self.reset_indexes();
                     }
                     _ => {}
@@ -230,12 +616,328 @@ impl<'a> Parser<'a> {
     }
 }
 
+/// return true if the attribute named like the layout attribute followed by `suffix`
+/// is a layout attribute: the suffix is empty, a breakpoint starting with a digit
+/// (`layout600px`) or a `-` (`layout-md`) if there are `named_breakpoints`,
+/// after a `c` for the containers.
+/// Other names only start like it, like `data-lang` for the `data-l` attribute
+/// or `data-layout-id` for `data-layout` without named breakpoints.
+fn is_layout_attribute_suffix(suffix: &str, named_breakpoints: bool) -> bool {
+    let breakpoint = suffix
+        .strip_prefix('c')
+        .filter(|breakpoint| !breakpoint.is_empty())
+        .unwrap_or(suffix);
+    breakpoint.is_empty()
+        || !breakpoint.starts_with(|c: char| {
+            c.is_ascii_alphabetic() || c == '_' || (c == '-' && !named_breakpoints)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::builder::LayoutElement;
 
     use super::*;
 
+    // diagnostics tests
+    #[test]
+    fn unknown_and_misplaced_classes_are_reported() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new("<row-l layout=\"gap:2 max-widht:60ch max-cols:3 p: pt:2x\"><div layout=\"gap:1\">");
+        parser.parse(&mut set);
+        let kinds: Vec<(&DiagnosticKind, &str)> = parser
+            .diagnostics
+            .iter()
+            .map(|d| (&d.kind, d.token.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (&DiagnosticKind::UnknownClass, "max-widht:60ch"),
+                (&DiagnosticKind::NotApplicable, "max-cols:3"),
+                (&DiagnosticKind::MissingValue, "p:"),
+                (&DiagnosticKind::InvalidValue, "pt:2x"),
+                (&DiagnosticKind::NotApplicable, "gap:1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn direction_is_a_class_of_row_only() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new("<row-l layout=\"direction:column\"><stack-l layout=\"direction:row\">");
+        parser.parse(&mut set);
+        assert!(set.iter().any(|element| matches!(
+            element,
+            LayoutElement::LayoutComponent(crate::components::Component::Row { direction: Some(direction), .. }, None)
+                if direction == "column"
+        )));
+        assert_eq!(parser.diagnostics.len(), 1);
+        assert_eq!(parser.diagnostics[0].kind, DiagnosticKind::NotApplicable);
+        assert_eq!(parser.diagnostics[0].token, "direction:row");
+    }
+
+    #[test]
+    fn malformed_breakpoint_attribute_is_reported() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new("<row-l layout600=\"gap:2\">");
+        parser.parse(&mut set);
+        assert_eq!(parser.diagnostics.len(), 1);
+        assert_eq!(
+            parser.diagnostics[0].to_string(),
+            "1:8: malformed breakpoint attribute `layout600` on <row-l>, expected something like `layout600px`"
+        );
+        assert_eq!(parser.diagnostics[0].token, "layout600");
+    }
+
+    #[test]
+    fn attributes_only_starting_like_the_layout_attribute_are_ignored() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new(
+            "<row-l data-l=\"gap:2\" data-lang=\"en\" data-l-id=\"a\" data-lc600px=\"gap:1\" data-l600=\"p:1\">",
+        );
+        parser.layout_attribute = "data-l".to_string();
+        parser.parse(&mut set);
+        assert_eq!(set.len(), 2);
+        // without named breakpoints, `data-l-id` is not a breakpoint attribute
+        assert_eq!(parser.diagnostics.len(), 1);
+        assert_eq!(parser.diagnostics[0].token, "data-l600");
+        assert_eq!(
+            parser.diagnostics[0].kind,
+            DiagnosticKind::MalformedBreakpoint {
+                attribute: "data-l".to_string()
+            }
+        );
+        assert!(parser.diagnostics[0].to_string().ends_with("expected something like `data-l600px`"));
+    }
+
+    // source tests
+    #[test]
+    fn named_breakpoints_are_resolved_or_reported() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new("<row-l layout-md=\"gap:1\" layout-xl=\"gap:2\">");
+        parser.breakpoints.insert("md".to_string(), 768);
+        parser.parse(&mut set);
+        assert!(set.iter().any(|element| matches!(
+            element,
+            LayoutElement::LayoutComponent(_, Some(MediaQuery::InferiorOrEqualTo(768)))
+        )));
+        assert_eq!(parser.diagnostics.len(), 1);
+        assert_eq!(parser.diagnostics[0].kind, DiagnosticKind::UnknownBreakpoint);
+        assert_eq!(parser.diagnostics[0].token, "layout-xl");
+    }
+
+    #[test]
+    fn container_breakpoints_dont_scope_the_layout_attribute() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new("<switcher-l layout=\"gap:2\" layoutc600px=\"gap:1\">");
+        parser.parse(&mut set);
+        assert_eq!(parser.biggest_breakpoint, None);
+        assert!(set.iter().any(|element| matches!(
+            element,
+            LayoutElement::LayoutComponent(_, Some(MediaQuery::ContainerInferiorOrEqualTo(600)))
+        )));
+        assert!(set
+            .iter()
+            .any(|element| matches!(element, LayoutElement::LayoutComponent(_, None))));
+    }
+
+    #[test]
+    fn comments_and_cdata_are_skipped() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new(
+            "<!-- <row-l layout=\"gap:3\"> --><![CDATA[ <div layout=\"p:1\"> ]]><div layout=\"p:2\">",
+        );
+        parser.parse(&mut set);
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::P("2".into()), None)));
+    }
+
+    #[test]
+    fn raw_text_elements_content_is_skipped() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new(
+            "<script>if (a<b) { x = '<div layout=\"p:1\">' }</SCRIPT>\
+             <style>div>p{}</style><textarea><row-l></textarea><div layout=\"p:2\">",
+        );
+        parser.parse(&mut set);
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::P("2".into()), None)));
+    }
+
+    #[test]
+    fn raw_text_ends_at_its_own_closing_tag() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new(
+            "<script>a = '</b><div layout=\"p:1\">' + '</scrip'</Script ><div layout=\"p:2\"><style>",
+        );
+        parser.parse(&mut set);
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::P("2".into()), None)));
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new(
+            "<script>var s='</scripts>'; '<row-l layout=\"gap:8\">'</script><style>a{}</styles><div layout=\"p:3\"></style/>",
+        );
+        parser.parse(&mut set);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn expressions_give_their_string_literals_as_values() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new(
+            "<div layout={open ? \"p:1\" : `p:${x} h:2`} layout600px={\"pt:3\"} class={a > b}>",
+        );
+        parser.parse(&mut set);
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::P("1".into()), None)));
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::H("2".into()), None)));
+        assert!(set.contains(&LayoutElement::LayoutUtility(
+            LayoutClass::PT("3".into()),
+            Some(MediaQuery::InferiorOrEqualTo(600))
+        )));
+        assert!(parser.diagnostics.is_empty());
+        let starts: Vec<usize> = parser
+            .sources
+            .iter()
+            .filter_map(|source| source.token.map(|span| span.start))
+            .collect();
+        assert!(starts.contains(&21));
+    }
+
+    #[test]
+    fn breakpoint_expressions_scope_the_layout_attribute_by_their_value_if_known() {
+        for (breakpoint_expression, scope) in [
+            ("{\"gap:1\"}", "gap:1"),
+            ("{wide ? \"gap:1\" : \"gap:3\"}", ""),
+            ("{`${a} gap:1 `}", ""),
+        ] {
+            let mut set: HashSet<LayoutElement> = HashSet::new();
+            let text = format!("<row-l layout=\"gap:2\" layout600px={breakpoint_expression}>");
+            Parser::new(&text).parse(&mut set);
+            assert!(
+                set.iter().any(|element| matches!(
+                    element,
+                    LayoutElement::LayoutComponent(_, Some(MediaQuery::SuperiorTo(600, v)))
+                        if v == scope
+                )),
+                "{breakpoint_expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn custom_layout_attribute_name() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new(
+            "<div data-layout=\"p:1\" data-layout600px=\"p:2\" data-layoutc400px=\"p:3\" layout=\"p:4\">",
+        );
+        parser.layout_attribute = "data-layout".to_string();
+        parser.parse(&mut set);
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::P("1".into()), None)));
+        assert!(set.contains(&LayoutElement::LayoutUtility(
+            LayoutClass::P("2".into()),
+            Some(MediaQuery::InferiorOrEqualTo(600))
+        )));
+        assert!(set.contains(&LayoutElement::LayoutUtility(
+            LayoutClass::P("3".into()),
+            Some(MediaQuery::ContainerInferiorOrEqualTo(400))
+        )));
+        assert!(!set.contains(&LayoutElement::LayoutUtility(LayoutClass::P("4".into()), None)));
+    }
+
+    #[test]
+    fn components_follow_the_element_naming() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new("<lc-row layout=\"gap:1 max-cols:2\"><row-l layout=\"gap:2\">");
+        parser.element_naming = ElementNaming::new("lc-{name}").unwrap();
+        parser.parse(&mut set);
+        assert!(set.iter().any(|element| matches!(
+            element,
+            LayoutElement::LayoutComponent(crate::components::Component::Row { gap: Some(gap), .. }, None) if gap == "1"
+        )));
+        assert_eq!(set.len(), 1);
+        assert_eq!(parser.diagnostics.len(), 2);
+        assert_eq!(parser.diagnostics[0].tag_name, "lc-row");
+        assert_eq!(parser.diagnostics[1].tag_name, "row-l");
+    }
+
+    #[test]
+    fn class_mode_reads_the_class_attribute() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser =
+            Parser::new("<div class=\"card row-l gap:2 p:1\" layout=\"p:3\" layout600px=\"gap:1\">");
+        parser.class_mode = true;
+        parser.parse(&mut set);
+        assert!(set.iter().any(|element| matches!(
+            element,
+            LayoutElement::LayoutComponent(crate::components::Component::Row { gap: Some(gap), .. }, None) if gap == "2"
+        )));
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::P("1".into()), None)));
+        assert_eq!(set.len(), 2);
+        // the classes have no breakpoints
+        assert_eq!(parser.diagnostics.len(), 1);
+        assert_eq!(parser.diagnostics[0].kind, DiagnosticKind::IgnoredInClassMode);
+        assert_eq!(parser.diagnostics[0].token, "layout600px");
+        assert_eq!(
+            parser.diagnostics[0].to_string(),
+            "1:48: breakpoint attribute `layout600px` on <div> is ignored in class mode"
+        );
+    }
+
+    #[test]
+    fn class_mode_reports_the_prefixed_classes() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new("<div class=\"card l-row l-gap-2\">");
+        parser.class_mode = true;
+        parser.parse(&mut set);
+        assert!(set.is_empty());
+        let messages: Vec<String> = parser.diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "1:18: class `l-row` on <div> is not read in class mode, write `row-l` instead",
+                "1:24: class `l-gap-2` on <div> is not read in class mode, write `gap:2` instead",
+            ]
+        );
+    }
+
+    #[test]
+    fn sources_keep_tag_and_token_spans() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let text = "<div>\n  <row-l layout=\"gap:2 p:1\">";
+        let mut parser = Parser::new(text);
+        parser.parse(&mut set);
+        assert_eq!(parser.sources.len(), 2);
+        for source in &parser.sources {
+            assert_eq!(&text[source.tag.start..source.tag.end], "row-l");
+            assert_eq!((source.tag.line, source.tag.column), (2, 4));
+            let attribute = source.attribute.unwrap();
+            assert_eq!(&text[attribute.start..attribute.end], "gap:2 p:1");
+            match &source.element {
+                LayoutElement::LayoutUtility(..) => {
+                    let token = source.token.unwrap();
+                    assert_eq!(&text[token.start..token.end], "p:1");
+                    assert_eq!((token.line, token.column), (2, 24));
+                }
+                LayoutElement::LayoutComponent(..) => assert_eq!(source.token, None),
+            }
+        }
+    }
+
+    #[test]
+    fn diagnostics_point_at_their_token() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let text = "<row-l layout=\"p:1 gapp:2 p:1 gapp:2\">";
+        let mut parser = Parser::new(text);
+        parser.parse(&mut set);
+        let starts: Vec<usize> = parser
+            .diagnostics
+            .iter()
+            .map(|d| d.span.unwrap().start)
+            .collect();
+        assert_eq!(starts, vec![19, 30]);
+    }
+
     // curly braces tests
     #[test]
     fn curly_braces_as_attribute_delimiters_working() {
@@ -263,6 +965,23 @@ mod tests {
         assert_eq!(parser.biggest_breakpoint_value, Some("p:3"));
     }
 
+    #[test]
+    fn mobile_first_scopes_the_layout_attribute_below_the_smallest_breakpoint() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser =
+            Parser::new("<row-l layout=\"gap:3\" layout900px=\"gap:2\" layout600px=\"gap:1\">");
+        parser.mobile_first = true;
+        parser.parse(&mut set);
+        assert!(set.iter().any(|element| matches!(
+            element,
+            LayoutElement::LayoutComponent(_, Some(MediaQuery::InferiorTo(600, value))) if value == "gap:1"
+        )));
+        assert!(set.iter().any(|element| matches!(
+            element,
+            LayoutElement::LayoutComponent(_, Some(MediaQuery::SuperiorOrEqualTo(900)))
+        )));
+    }
+
     #[test]
     fn media_query_update_biggest_breakpoint_of_parser() {
         let mut set: HashSet<LayoutElement> = HashSet::new();
@@ -434,6 +1153,52 @@ mod tests {
         assert_eq!(parser.transition('"'), Some(ReadingAttributeValue));
     }
 
+    #[test]
+    fn waiting_attribute_value_and_single_quote_return_reading_single_quoted_attribute_value() {
+        let mut parser = Parser::new("");
+        parser.state = WaitingAttributeValue;
+        assert_eq!(parser.transition('\''), Some(ReadingSingleQuotedAttributeValue));
+    }
+
+    #[test]
+    fn reading_single_quoted_attribute_value_and_single_quote_return_after_tag_name() {
+        let mut parser = Parser::new("");
+        parser.state = ReadingSingleQuotedAttributeValue;
+        assert_eq!(parser.transition('"'), None);
+        assert_eq!(parser.transition(' '), None);
+        assert_eq!(parser.transition('\''), Some(AfterTagName));
+    }
+
+    #[test]
+    fn waiting_attribute_value_and_other_character_return_reading_unquoted_attribute_value() {
+        let mut parser = Parser::new("");
+        parser.state = WaitingAttributeValue;
+        assert_eq!(parser.transition('p'), Some(ReadingUnquotedAttributeValue));
+    }
+
+    #[test]
+    fn reading_unquoted_attribute_value_ends_with_whitespace_or_right_chevron() {
+        let mut parser = Parser::new("");
+        parser.state = ReadingUnquotedAttributeValue;
+        assert_eq!(parser.transition(':'), None);
+        assert_eq!(parser.transition(' '), Some(AfterTagName));
+        assert_eq!(parser.transition('>'), Some(Resting));
+    }
+
+    #[test]
+    fn single_quoted_and_unquoted_layout_attributes_generate_elements() {
+        let mut set: HashSet<LayoutElement> = HashSet::new();
+        let mut parser = Parser::new("<div layout='p:2 h:3' layout600px=pt:1><div layout=px:4>");
+        parser.parse(&mut set);
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::P("2".into()), None)));
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::H("3".into()), None)));
+        assert!(set.contains(&LayoutElement::LayoutUtility(
+            LayoutClass::PT("1".into()),
+            Some(MediaQuery::InferiorOrEqualTo(600))
+        )));
+        assert!(set.contains(&LayoutElement::LayoutUtility(LayoutClass::PX("4".into()), None)));
+    }
+
     #[test]
     fn reading_attribute_name_and_equal_return_waiting_attribute_value() {
         let mut parser = Parser::new("");
@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::config::LayoutStyleConfig;
+use crate::media_query::MediaQuery;
+use crate::naming::SelectorNames;
+use crate::parser::ElementSource;
+use crate::span::Span;
+
+/// Where a rule starts in the stylesheet being assembled, before the transformations
+/// (renamings, `:where()`) which only change the text inside the lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RulePosition {
+    /// byte index of the first selector of the rule
+    pub offset: usize,
+    pub media_query: Option<MediaQuery>,
+    /// the rule as it was generated, before being scoped to its breakpoint
+    pub rule: String,
+}
+
+/// The first markup location of every generated rule,
+/// in the documents given to `record`.
+#[derive(Debug, Default)]
+pub struct RuleOrigins {
+    /// name of each document, like the path of the template
+    sources: Vec<String>,
+    /// index of the document and span of the markup producing the rule
+    rules: HashMap<(Option<MediaQuery>, String), (usize, Span)>,
+}
+
+impl RuleOrigins {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a document named `name`, return its index for `record`.
+    pub fn add_source(&mut self, name: &str) -> usize {
+        self.sources.push(name.to_string());
+        self.sources.len() - 1
+    }
+
+    /// Keep the markup location of the rules generated by the elements of the document,
+    /// the rules already found in a previous element keep their location.
+    pub fn record(
+        &mut self,
+        source: usize,
+        elements: &[ElementSource],
+        layout_style_config: &LayoutStyleConfig,
+    ) {
+        let names = SelectorNames::from_config(layout_style_config);
+        for element_source in elements {
+            // the class producing the element, or the layout attribute for components
+            let span = element_source
+                .token
+                .or(element_source.attribute)
+                .unwrap_or(element_source.tag);
+            let mut rules = HashSet::new();
+            let mut mq_rules = HashMap::new();
+            element_source.element.clone().insert_css_with_names(
+                layout_style_config.harmonic_ratio,
+                &names,
+                &mut rules,
+                &mut mq_rules,
+            );
+            let keys =
+                rules
+                    .into_iter()
+                    .map(|rule| (None, rule))
+                    .chain(mq_rules.into_iter().flat_map(|(mq, rules)| {
+                        rules.into_iter().map(move |rule| (Some(mq.clone()), rule))
+                    }));
+            for key in keys {
+                self.rules.entry(key).or_insert((source, span));
+            }
+        }
+    }
+
+    /// return the origin of the rule, if it comes from one of the documents
+    pub fn origin(&self, media_query: &Option<MediaQuery>, rule: &str) -> Option<(usize, Span)> {
+        self.rules
+            .get(&(media_query.clone(), rule.to_string()))
+            .copied()
+    }
+
+    /// Write the source map (version 3) of the stylesheet `css` named `file`,
+    /// with a mapping from the start of every rule to the markup producing it.
+    pub fn source_map(&self, file: &str, css: &str, positions: &[RulePosition]) -> String {
+        let mut mappings: Vec<(usize, usize, usize, Span)> = positions
+            .iter()
+            .filter_map(|position| {
+                let (source, span) = self.origin(&position.media_query, &position.rule)?;
+                let (line, column) = line_and_column(css, position.offset);
+                Some((line, column, source, span))
+            })
+            .collect();
+        mappings.sort_by_key(|(line, column, ..)| (*line, *column));
+
+        let sources: Vec<String> = self.sources.iter().map(|s| json_string(s)).collect();
+        format!(
+            "{{\"version\":3,\"file\":{},\"sources\":[{}],\"names\":[],\"mappings\":{}}}",
+            json_string(file),
+            sources.join(","),
+            json_string(&encode_mappings(&mappings)),
+        )
+    }
+}
+
+/// return the line and the column (counted in chars) of the byte index, both from 0
+fn line_and_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count(),
+        before[line_start..].chars().count(),
+    )
+}
+
+/// encode the mappings, sorted by generated line and column, in the `mappings` format:
+/// a group of segments by line, each segment being relative to the previous one
+fn encode_mappings(mappings: &[(usize, usize, usize, Span)]) -> String {
+    let mut encoded = String::new();
+    let mut current_line = 0;
+    let mut previous_column = 0;
+    let mut previous_source = 0;
+    let mut previous_source_line = 0;
+    let mut previous_source_column = 0;
+    for (line, column, source, span) in mappings {
+        if *line > current_line {
+            encoded.push_str(&";".repeat(line - current_line));
+            current_line = *line;
+            // the generated column is relative to the start of the line
+            previous_column = 0;
+        } else if !encoded.is_empty() && !encoded.ends_with(';') {
+            encoded.push(',');
+        }
+        // spans count lines and columns from 1
+        let source_line = span.line as i64 - 1;
+        let source_column = span.column as i64 - 1;
+        for value in [
+            *column as i64 - previous_column,
+            *source as i64 - previous_source,
+            source_line - previous_source_line,
+            source_column - previous_source_column,
+        ] {
+            encode_vlq(value, &mut encoded);
+        }
+        previous_column = *column as i64;
+        previous_source = *source as i64;
+        previous_source_line = source_line;
+        previous_source_column = source_column;
+    }
+    encoded
+}
+
+/// append the value in base64 VLQ, the sign being the lowest bit of the first digit
+fn encode_vlq(value: i64, output: &mut String) {
+    const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut vlq = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = (vlq & 0b11111) as usize;
+        vlq >>= 5;
+        if vlq > 0 {
+            // continuation bit
+            digit |= 0b100000;
+        }
+        output.push(BASE64[digit] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+/// return the text as a json string, between quotes
+fn json_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vlq_values() {
+        let mut output = String::new();
+        for value in [0, 1, -1, 16, 123] {
+            encode_vlq(value, &mut output);
+        }
+        assert_eq!(output, "ACDgB2H");
+    }
+
+    #[test]
+    fn rules_point_to_the_first_class_producing_them() {
+        let config = crate::config::LayoutStyleConfig::default();
+        let (mut rules, mut mq_rules) = (HashSet::new(), HashMap::new());
+        let mut origins = RuleOrigins::new();
+        for (name, text) in [
+            ("a.html", "<p>\n<row-l layout=\"p:1\">"),
+            ("b.html", "<a layout=\"p:1\">"),
+        ] {
+            let source = origins.add_source(name);
+            crate::insert_css_rules_with_origins(
+                text,
+                &mut rules,
+                &mut mq_rules,
+                &config,
+                &mut origins,
+                source,
+            );
+        }
+        let utility = rules.iter().find(|rule| rule.contains("p:1")).unwrap();
+        let (source, span) = origins.origin(&None, utility).unwrap();
+        assert_eq!((source, span.line, span.column), (0, 2, 16));
+
+        let (css, map) = crate::generate_final_css_with_source_map(
+            &rules, &mq_rules, &config, &origins, "s.css",
+        );
+        assert_eq!(css, crate::generate_final_css_with_config(&rules, &mq_rules, &config));
+        assert!(map
+            .starts_with("{\"version\":3,\"file\":\"s.css\",\"sources\":[\"a.html\",\"b.html\"]"));
+    }
+
+    #[test]
+    fn mappings_are_relative() {
+        let span = |line, column| Span {
+            start: 0,
+            end: 0,
+            line,
+            column,
+        };
+        let mappings = [
+            (1, 2, 0, span(1, 5)),
+            (1, 10, 0, span(3, 1)),
+            (3, 0, 1, span(2, 1)),
+        ];
+        assert_eq!(encode_mappings(&mappings), ";EAAI,QAEJ;;ACDA");
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+/// Position of a piece of the parsed text.
+/// `start` and `end` are byte indexes (end excluded),
+/// `line` and `column` start at 1 and locate `start`, the column is counted in characters.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Index of the beginning of every line of a text,
+/// so we can find the line and column of a byte index without rescanning the text.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        LineIndex { line_starts }
+    }
+
+    /// create the span of `text[start..end]`
+    pub fn span(&self, text: &str, start: usize, end: usize) -> Span {
+        // index of the last line starting before or at `start`
+        let line = self.line_starts.partition_point(|&line_start| line_start <= start);
+        let line_start = self.line_starts[line - 1];
+        Span {
+            start,
+            end,
+            line,
+            column: text[line_start..start].chars().count() + 1,
+        }
+    }
+}
+
+/// return every whitespace separated token of `text` with its byte index in `text`
+pub fn tokens_with_offsets(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_whitespace()
+        .map(move |token| (token.as_ptr() as usize - text.as_ptr() as usize, token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_on_first_line() {
+        let text = "<row-l layout=\"gap:2\">";
+        let index = LineIndex::new(text);
+        let span = index.span(text, 1, 6);
+        assert_eq!((span.line, span.column), (1, 2));
+    }
+
+    #[test]
+    fn span_after_new_lines_count_columns_in_chars() {
+        let text = "<p>\n  é<row-l>";
+        let index = LineIndex::new(text);
+        let span = index.span(text, 9, 14);
+        assert_eq!(&text[span.start..span.end], "row-l");
+        assert_eq!((span.line, span.column), (2, 5));
+    }
+
+    #[test]
+    fn tokens_keep_their_offsets() {
+        let tokens: Vec<(usize, &str)> = tokens_with_offsets(" gap:2  p:1").collect();
+        assert_eq!(tokens, vec![(1, "gap:2"), (8, "p:1")]);
+    }
+}
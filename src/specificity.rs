@@ -0,0 +1,271 @@
+/// Wrap every selector of the css in `:where()`, so all the rules have no specificity
+/// and any style of the page overrides them.
+/// The whole selector is wrapped, not only its `[layout...]` parts: a component tag
+/// would otherwise beat the utilities, like `box-l{display:block}` beating `hide-over`.
+/// Without specificity the last rule wins, so the rules must have been sorted
+/// with ordering::sorted_rules_by_specificity for the more specific ones to still win.
+pub fn remove_specificity(css: &str) -> String {
+    let mut output = String::with_capacity(css.len() + css.len() / 4);
+    let mut prelude = String::new();
+    let mut in_declarations = false;
+    let mut quote: Option<char> = None;
+    for c in css.chars() {
+        if let Some(q) = quote {
+            match in_declarations {
+                true => output.push(c),
+                false => prelude.push(c),
+            }
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        if in_declarations {
+            match c {
+                '"' | '\'' => quote = Some(c),
+                '}' => in_declarations = false,
+                _ => {}
+            }
+            output.push(c);
+            continue;
+        }
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                prelude.push(c);
+            }
+            '{' => {
+                if prelude.trim_start().starts_with('@') {
+                    output.push_str(&prelude);
+                } else {
+                    output.push_str(&where_selectors(&prelude));
+                    in_declarations = true;
+                }
+                output.push(c);
+                prelude.clear();
+            }
+            // end of an at-rule block or of an at-rule statement like `@layer a, b;`
+            '}' | ';' => {
+                output.push_str(&prelude);
+                output.push(c);
+                prelude.clear();
+            }
+            _ => prelude.push(c),
+        }
+    }
+    output.push_str(&prelude);
+    output
+}
+
+/// wrap each selector of the list in `:where()`, keeping the whitespace around the list
+fn where_selectors(selector_list: &str) -> String {
+    let trimmed = selector_list.trim();
+    if trimmed.is_empty() {
+        return selector_list.to_string();
+    }
+    let leading = &selector_list[..selector_list.len() - selector_list.trim_start().len()];
+    let trailing = &selector_list[selector_list.trim_end().len()..];
+    let selectors: Vec<String> = split_selector_list(trimmed)
+        .into_iter()
+        .map(|selector| {
+            let selector = selector.trim();
+            // a pseudo-element can't be inside of `:where()`
+            match selector.find("::") {
+                Some(0) => selector.to_string(),
+                Some(i) => format!(":where({}){}", &selector[..i], &selector[i..]),
+                None => format!(":where({selector})"),
+            }
+        })
+        .collect();
+    format!("{leading}{}{trailing}", selectors.join(", "))
+}
+
+/// return the specificity (ids, classes, types) of the first rule of the css,
+/// the highest of its selector list
+pub fn rule_specificity(css: &str) -> (usize, usize, usize) {
+    let mut quote: Option<char> = None;
+    let prelude_end = css
+        .char_indices()
+        .find(|&(_, c)| match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+                false
+            }
+            None => {
+                if c == '"' || c == '\'' {
+                    quote = Some(c);
+                }
+                c == '{'
+            }
+        })
+        .map_or(css.len(), |(i, _)| i);
+    selector_list_specificity(&css[..prelude_end])
+}
+
+/// return the highest specificity of the selectors of the list
+fn selector_list_specificity(selector_list: &str) -> (usize, usize, usize) {
+    split_selector_list(selector_list)
+        .into_iter()
+        .map(selector_specificity)
+        .max()
+        .unwrap_or_default()
+}
+
+/// return the specificity of a complex selector, like `row-l[layout~="gap:1"] > *`
+fn selector_specificity(selector: &str) -> (usize, usize, usize) {
+    let (mut ids, mut classes, mut types) = (0, 0, 0);
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '-' || c == '_' || c == '\\';
+    let mut rest = selector;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        match c {
+            '[' => {
+                classes += 1;
+                rest = after_closing(rest, ']');
+            }
+            '#' | '.' => {
+                match c {
+                    '#' => ids += 1,
+                    _ => classes += 1,
+                }
+                rest = rest.trim_start_matches(is_name_char);
+            }
+            ':' => {
+                let pseudo_element = rest.starts_with(':');
+                let name = rest.trim_start_matches(':');
+                let name_end = name.find(|c: char| !is_name_char(c)).unwrap_or(name.len());
+                let arguments = name[name_end..].strip_prefix('(');
+                rest = match arguments {
+                    Some(arguments) => after_closing(arguments, ')'),
+                    None => &name[name_end..],
+                };
+                let inner = arguments.map(|arguments| {
+                    &arguments[..arguments.len() - after_closing(arguments, ')').len() - 1]
+                });
+                match (&name[..name_end], inner) {
+                    _ if pseudo_element => types += 1,
+                    ("where", _) => {}
+                    // the specificity of their most specific argument
+                    ("is" | "not" | "has", Some(inner)) => {
+                        let (a, b, c) = selector_list_specificity(inner);
+                        ids += a;
+                        classes += b;
+                        types += c;
+                    }
+                    _ => classes += 1,
+                }
+            }
+            c if is_name_char(c) => {
+                types += 1;
+                rest = rest.trim_start_matches(is_name_char);
+            }
+            _ => {}
+        }
+    }
+    (ids, classes, types)
+}
+
+/// return the text after the `closing` bracket ending the text,
+/// skipping the quotes and the nested brackets
+fn after_closing(text: &str, closing: char) -> &str {
+    let opening = match closing {
+        ']' => '[',
+        _ => '(',
+    };
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if c == opening => depth += 1,
+            (None, c) if c == closing && depth == 0 => return &text[i + 1..],
+            (None, c) if c == closing => depth -= 1,
+            _ => {}
+        }
+    }
+    ""
+}
+
+/// split the selector list on the commas which are not in parentheses or quotes
+fn split_selector_list(selector_list: &str) -> Vec<&str> {
+    let mut selectors = vec![];
+    let mut parentheses = 0;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in selector_list.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => parentheses += 1,
+            (None, ')') => parentheses -= 1,
+            (None, ',') if parentheses == 0 => {
+                selectors.push(&selector_list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    selectors.push(&selector_list[start..]);
+    selectors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_selector_is_wrapped() {
+        assert_eq!(
+            remove_specificity("\n  row-l > *,\n  stack-l:is(a,b) {\n  gap: 1rem;\n}"),
+            "\n  :where(row-l > *), :where(stack-l:is(a,b)) {\n  gap: 1rem;\n}"
+        );
+        assert_eq!(
+            remove_specificity("row-l[layout~=\"a{b\"]::before{content:\"}\"}"),
+            ":where(row-l[layout~=\"a{b\"])::before{content:\"}\"}"
+        );
+    }
+
+    #[test]
+    fn specificity_is_the_one_of_the_most_specific_selector() {
+        assert_eq!(rule_specificity("\n  row-l{display:flex}"), (0, 0, 1));
+        assert_eq!(
+            rule_specificity("icon-l[layout*=\"gap-dir:end\"][layout*=\"gap:1\"] >:nth-child(1){}"),
+            (0, 3, 1)
+        );
+        assert_eq!(
+            rule_specificity("a > :not(outsider-l[layout~=\"a{b\"]), #b::before{}"),
+            (1, 0, 1)
+        );
+        assert_eq!(
+            rule_specificity("a > :not(outsider-l[layout~=\"a{b\"]):where(.c){}"),
+            (0, 1, 2)
+        );
+    }
+
+    #[test]
+    fn at_rules_are_kept() {
+        assert_eq!(
+            remove_specificity("@layer a, b;@layer a{@media (width <= 600px){p{margin:0}}}"),
+            "@layer a, b;@layer a{@media (width <= 600px){:where(p){margin:0}}}"
+        );
+    }
+
+    #[test]
+    fn low_specificity_keeps_the_more_specific_rules_last() {
+        let config = crate::config::LayoutStyleConfig::from_toml_str("low_specificity = true").unwrap();
+        let (css, _) = crate::get_css_and_diagnostics(
+            "<icon-l layout=\"gap:1 gap-dir:end\"></icon-l><icon-l layout=\"gap:1\">",
+            None,
+            None,
+            &config,
+        );
+        let plain = css.find(":where(icon-l[layout*=\"gap:1\"] >:nth-child(1))").unwrap();
+        let gap_dir = css.find(":where(icon-l[layout*=\"gap-dir:end\"]").unwrap();
+        assert!(plain < gap_dir);
+    }
+}
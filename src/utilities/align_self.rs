@@ -1,10 +1,12 @@
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
 
-pub fn align_self_css(value: &str, set: &mut HashSet<String>) {
+pub fn align_self_css(value: &str, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     set.insert(formatdoc!(
         r#"
-        [layout~="align-self:{value}"]{{
+        [{layout}~="align-self:{value}"]{{
             align-self: {value};
         }}
         "#
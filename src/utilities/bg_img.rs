@@ -1,19 +1,26 @@
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
-const BG_IMG_STYLE: &str = r#"
-    [layout*="bg-img"]{
+fn bg_img_style(names: &SelectorNames) -> String {
+    let layout = &names.attribute;
+    format!(
+        r#"
+    [{layout}*="bg-img"]{{
         background-origin: border-box;
         background-repeat: no-repeat;
         background-position: center;
         background-size: cover;
-    }
-"#;
+    }}
+"#
+    )
+}
 
-pub fn bg_img_css(value: &str, set: &mut HashSet<String>) {
-    set.insert(BG_IMG_STYLE.to_string());
+pub fn bg_img_css(value: &str, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
+    set.insert(bg_img_style(names));
     set.insert(formatdoc!(
         r#"
-        [layout~="bg-img:{value}"]{{
+        [{layout}~="bg-img:{value}"]{{
             background-image: url({value});
         }}
         "#
@@ -0,0 +1,18 @@
+use crate::naming::SelectorNames;
+use std::collections::HashSet;
+// makes the element a query container,
+// so the `layoutc` attributes of its children use its width
+fn container_style(names: &SelectorNames) -> String {
+    let layout = &names.attribute;
+    format!(
+        r#"
+    [{layout}~="container"] {{
+        container-type: inline-size;
+    }}
+"#
+    )
+}
+
+pub fn container_css(names: &SelectorNames, set: &mut HashSet<String>) {
+    set.insert(container_style(names));
+}
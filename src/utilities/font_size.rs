@@ -1,12 +1,19 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 use std::collections::HashSet;
 
-pub fn font_size_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn font_size_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="font-size:{value}"]{{
+        [{layout}~="font-size:{value}"]{{
             font-size: {harmonic_value};
         }}
         "#
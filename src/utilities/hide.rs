@@ -1,10 +1,12 @@
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
-pub fn hide_over_css(value: &str, set: &mut HashSet<String>) {
+pub fn hide_over_css(value: &str, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     set.insert(formatdoc!(
         r#"
         @media screen and (min-width: {value}) {{
-            [layout~="hide-over:{value}"]{{
+            [{layout}~="hide-over:{value}"]{{
                 display:none;
             }}
         }}
@@ -12,11 +14,12 @@ pub fn hide_over_css(value: &str, set: &mut HashSet<String>) {
     ));
 }
 
-pub fn hide_under_css(value: &str, set: &mut HashSet<String>) {
+pub fn hide_under_css(value: &str, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     set.insert(formatdoc!(
         r#"
         @media screen and (max-width: {value}) {{
-            [layout~="hide-under:{value}"]{{
+            [{layout}~="hide-under:{value}"]{{
                 display:none;
             }}
         }}
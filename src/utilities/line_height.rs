@@ -1,9 +1,11 @@
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
-pub fn line_height_css(value: &str, set: &mut HashSet<String>) {
+pub fn line_height_css(value: &str, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     set.insert(formatdoc!(
         r#"
-        [layout~="line-height:{value}"]{{
+        [{layout}~="line-height:{value}"]{{
             line-height: {value};
         }}
         "#
@@ -1,5 +1,6 @@
 pub mod align_self;
 pub mod bg_img;
+pub mod container;
 pub mod flex;
 pub mod font_size;
 pub mod h;
@@ -1,12 +1,14 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
 use std::collections::HashSet;
-pub fn p_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn p_css(value: &str, harmonic_ratio: f64, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="p:{value}"]{{
+        [{layout}~="p:{value}"]{{
             padding: {harmonic_value};
             --pl: {harmonic_value};
             --pr: {harmonic_value};
@@ -15,33 +17,36 @@ pub fn p_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
     ));
 }
 
-pub fn pt_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pt_css(value: &str, harmonic_ratio: f64, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pt:{value}"]{{
+        [{layout}~="pt:{value}"]{{
             padding-top: {harmonic_value};
         }}
         "#
     ));
 }
 
-pub fn pb_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pb_css(value: &str, harmonic_ratio: f64, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pb:{value}"]{{
+        [{layout}~="pb:{value}"]{{
             padding-bottom: {harmonic_value};
         }}
         "#
     ));
 }
 
-pub fn pl_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pl_css(value: &str, harmonic_ratio: f64, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pl:{value}"]{{
+        [{layout}~="pl:{value}"]{{
             padding-left: {harmonic_value};
             --pl: {harmonic_value};
         }}
@@ -49,11 +54,12 @@ pub fn pl_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
     ));
 }
 
-pub fn pr_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pr_css(value: &str, harmonic_ratio: f64, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pr:{value}"]{{
+        [{layout}~="pr:{value}"]{{
             padding-right: {harmonic_value};
             --pr: {harmonic_value};
         }}
@@ -61,11 +67,12 @@ pub fn pr_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
     ));
 }
 
-pub fn px_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn px_css(value: &str, harmonic_ratio: f64, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="px:{value}"]{{
+        [{layout}~="px:{value}"]{{
             padding-left: {harmonic_value};
             padding-right: {harmonic_value};
             --pl: {harmonic_value};
@@ -75,11 +82,12 @@ pub fn px_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
     ));
 }
 
-pub fn py_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn py_css(value: &str, harmonic_ratio: f64, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="py:{value}"]{{
+        [{layout}~="py:{value}"]{{
             padding-top: {harmonic_value};
             padding-bottom: {harmonic_value};
         }}
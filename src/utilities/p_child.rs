@@ -1,12 +1,19 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
 use std::collections::HashSet;
-pub fn p_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn p_child_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="p-child:{value}"]> *{{
+        [{layout}~="p-child:{value}"]> *{{
             padding: {harmonic_value};
             --pl: padding: {harmonic_value};
             --pr: padding: {harmonic_value};
@@ -15,33 +22,51 @@ pub fn p_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>)
     ));
 }
 
-pub fn pt_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pt_child_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pt-child:{value}"]> *{{
+        [{layout}~="pt-child:{value}"]> *{{
             padding-top: {harmonic_value};
         }}
         "#
     ));
 }
 
-pub fn pb_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pb_child_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pb-child:{value}"]> *{{
+        [{layout}~="pb-child:{value}"]> *{{
             padding-bottom: {harmonic_value};
         }}
         "#
     ));
 }
 
-pub fn pl_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pl_child_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pl-child:{value}"]> *{{
+        [{layout}~="pl-child:{value}"]> *{{
             padding-left: {harmonic_value};
             --pl: {harmonic_value};
         }}
@@ -49,11 +74,17 @@ pub fn pl_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>)
     ));
 }
 
-pub fn pr_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pr_child_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pr-child:{value}"]> *{{
+        [{layout}~="pr-child:{value}"]> *{{
             padding-right: {harmonic_value};
             --pr: {harmonic_value};
         }}
@@ -61,11 +92,17 @@ pub fn pr_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>)
     ));
 }
 
-pub fn px_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn px_child_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="px-child:{value}"]> *{{
+        [{layout}~="px-child:{value}"]> *{{
             padding-left: {harmonic_value};
             padding-right: {harmonic_value};
             --pl: {harmonic_value};
@@ -75,11 +112,17 @@ pub fn px_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>)
     ));
 }
 
-pub fn py_child_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn py_child_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="py-child:{value}"]> *{{
+        [{layout}~="py-child:{value}"]> *{{
             padding-top: {harmonic_value};
             padding-bottom: {harmonic_value};
         }}
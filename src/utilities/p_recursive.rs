@@ -1,12 +1,19 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
 use std::collections::HashSet;
-pub fn p_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn p_recursive_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="p-recursive:{value}"] *{{
+        [{layout}~="p-recursive:{value}"] *{{
             padding: {harmonic_value};
             --pl: padding: {harmonic_value};
             --pr: padding: {harmonic_value};
@@ -15,33 +22,51 @@ pub fn p_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<Strin
     ));
 }
 
-pub fn pt_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pt_recursive_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pt-recursive:{value}"] *{{
+        [{layout}~="pt-recursive:{value}"] *{{
             padding-top: {harmonic_value};
         }}
         "#
     ));
 }
 
-pub fn pb_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pb_recursive_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pb-recursive:{value}"] *{{
+        [{layout}~="pb-recursive:{value}"] *{{
             padding-bottom: {harmonic_value};
         }}
         "#
     ));
 }
 
-pub fn pl_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pl_recursive_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pl-recursive:{value}"] *{{
+        [{layout}~="pl-recursive:{value}"] *{{
             padding-left: {harmonic_value};
             --pl: {harmonic_value};
         }}
@@ -49,11 +74,17 @@ pub fn pl_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<Stri
     ));
 }
 
-pub fn pr_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn pr_recursive_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="pr-recursive:{value}"] *{{
+        [{layout}~="pr-recursive:{value}"] *{{
             padding-right: {harmonic_value};
             --pr: {harmonic_value};
         }}
@@ -61,11 +92,17 @@ pub fn pr_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<Stri
     ));
 }
 
-pub fn px_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn px_recursive_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="px-recursive:{value}"] *{{
+        [{layout}~="px-recursive:{value}"] *{{
             padding-left: {harmonic_value};
             padding-right: {harmonic_value};
             --pl: {harmonic_value};
@@ -75,11 +112,17 @@ pub fn px_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<Stri
     ));
 }
 
-pub fn py_recursive_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn py_recursive_css(
+    value: &str,
+    harmonic_ratio: f64,
+    names: &SelectorNames,
+    set: &mut HashSet<String>,
+) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(&value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="py-recursive:{value}"] *{{
+        [{layout}~="py-recursive:{value}"] *{{
             padding-top: {harmonic_value};
             padding-bottom: {harmonic_value};
         }}
@@ -1,17 +1,24 @@
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
-const RATIO_STYLE: &str = r#"
-    img[layout~="ratio"],video[layout~="ratio"] {
+fn ratio_style(names: &SelectorNames) -> String {
+    let layout = &names.attribute;
+    format!(
+        r#"
+    img[{layout}~="ratio"],video[{layout}~="ratio"] {{
     inline-size: 100%;
     object-fit: cover;
-    }
-"#;
+    }}
+"#
+    )
+}
 
-pub fn ratio_css(value: &str, set: &mut HashSet<String>) {
-    set.insert(RATIO_STYLE.to_string());
+pub fn ratio_css(value: &str, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
+    set.insert(ratio_style(names));
     set.insert(formatdoc!(
         r#"
-        [layout~="ratio:{value}"]{{
+        [{layout}~="ratio:{value}"]{{
             aspect-ratio: {value};
         }}
         "#
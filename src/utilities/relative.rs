@@ -1,11 +1,17 @@
+use crate::naming::SelectorNames;
 use std::collections::HashSet;
-const RELATIVE_STYLE: &str = r#"
-    [layout~="relative"] {
+fn relative_style(names: &SelectorNames) -> String {
+    let layout = &names.attribute;
+    format!(
+        r#"
+    [{layout}~="relative"] {{
         position: relative;
         min-height: 1px;
-    }
-"#;
+    }}
+"#
+    )
+}
 
-pub fn relative_css(set: &mut HashSet<String>) {
-    set.insert(RELATIVE_STYLE.to_string());
+pub fn relative_css(names: &SelectorNames, set: &mut HashSet<String>) {
+    set.insert(relative_style(names));
 }
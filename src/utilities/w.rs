@@ -1,12 +1,14 @@
 use indoc::formatdoc;
 use crate::harmonic::get_harmonic;
+use crate::naming::SelectorNames;
 
 use std::collections::HashSet;
-pub fn w_css(value: &str, harmonic_ratio: f64, set: &mut HashSet<String>) {
+pub fn w_css(value: &str, harmonic_ratio: f64, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     let harmonic_value = get_harmonic(value, harmonic_ratio);
     set.insert(formatdoc!(
         r#"
-        [layout~="w:{value}"]{{
+        [{layout}~="w:{value}"]{{
             width: {harmonic_value};
         }}
         "#
@@ -1,9 +1,11 @@
+use crate::naming::SelectorNames;
 use indoc::formatdoc;
 use std::collections::HashSet;
-pub fn z_index_css(value: &str, set: &mut HashSet<String>) {
+pub fn z_index_css(value: &str, names: &SelectorNames, set: &mut HashSet<String>) {
+    let layout = &names.attribute;
     set.insert(formatdoc!(
         r#"
-        [layout~="z-index:{value}"]{{
+        [{layout}~="z-index:{value}"]{{
             z-index: {value};
         }}
         "#